@@ -0,0 +1,288 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::PathBuf,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    thread::{self, JoinHandle},
+};
+
+use image::{
+    Delay, Frame, RgbaImage,
+    buffer::ConvertBuffer,
+    codecs::gif::{GifEncoder, Repeat},
+};
+
+use crate::{
+    bitmap::{BitmapError, ImageStyle, image_size, render},
+    game::Game,
+    grid::Region,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct GifOptions {
+    pub generations: usize,
+    pub frame_delay_ms: u32,
+    pub style: ImageStyle,
+    /// Crop every frame to the bounding box of the whole run.
+    pub crop: bool,
+    pub looping: bool,
+}
+
+impl Default for GifOptions {
+    fn default() -> Self {
+        Self {
+            generations: 100,
+            frame_delay_ms: 100,
+            style: ImageStyle {
+                cell_size: 4,
+                ..ImageStyle::default()
+            },
+            crop: true,
+            looping: true,
+        }
+    }
+}
+
+impl GifOptions {
+    /// Generations simulated in total, counting the cropping pass.
+    pub fn total_steps(&self) -> usize {
+        if self.crop {
+            self.generations * 2
+        } else {
+            self.generations
+        }
+    }
+}
+
+/// Writes one frame per generation starting from the game's current state.
+/// `progress` is bumped after every simulated generation.
+pub fn write_gif<W: Write>(
+    game: &Game,
+    writer: W,
+    options: &GifOptions,
+    progress: &AtomicUsize,
+) -> Result<(), BitmapError> {
+    let region = if options.crop {
+        run_bounds(game.clone(), options.generations, progress)
+    } else {
+        None
+    };
+    let frame_size = region.map_or((game.grid.width(), game.grid.height()), |r| {
+        (r.width, r.height)
+    });
+    image_size(frame_size, &options.style)?;
+
+    let mut encoder = GifEncoder::new_with_speed(writer, 30);
+    let repeat = if options.looping {
+        Repeat::Infinite
+    } else {
+        Repeat::Finite(0)
+    };
+    encoder
+        .set_repeat(repeat)
+        .map_err(|e| BitmapError::Image(e.to_string()))?;
+
+    let delay = Delay::from_numer_denom_ms(options.frame_delay_ms, 1);
+    let mut game = game.clone();
+    for _ in 0..options.generations {
+        let grid = match region {
+            Some(region) => game
+                .grid
+                .cropped(region)
+                .expect("write_gif: run bounds outside of grid"),
+            None => game.grid.clone(),
+        };
+
+        let image: RgbaImage = render(&grid, &options.style)?.convert();
+        encoder
+            .encode_frame(Frame::from_parts(image, 0, 0, delay))
+            .map_err(|e| BitmapError::Image(e.to_string()))?;
+
+        game.tick();
+        progress.fetch_add(1, Ordering::Relaxed);
+    }
+
+    Ok(())
+}
+
+fn run_bounds(mut game: Game, generations: usize, progress: &AtomicUsize) -> Option<Region> {
+    let mut bounds: Option<Region> = None;
+    for _ in 0..generations {
+        if let Some(b) = game.grid.live_bounds() {
+            bounds = Some(bounds.map_or(b, |r| r.union(b)));
+        }
+        game.tick();
+        progress.fetch_add(1, Ordering::Relaxed);
+    }
+    bounds
+}
+
+/// A GIF export running on its own thread.
+pub struct GifExport {
+    pub path: PathBuf,
+    progress: Arc<AtomicUsize>,
+    total: usize,
+    handle: Option<JoinHandle<Result<(), BitmapError>>>,
+}
+
+impl GifExport {
+    /// Refuses frames too large to render before starting; a cropped run is
+    /// checked on the thread once its bounds are known.
+    pub fn start(game: Game, path: PathBuf, options: GifOptions) -> Result<Self, BitmapError> {
+        if !options.crop {
+            image_size((game.grid.width(), game.grid.height()), &options.style)?;
+        }
+        let progress = Arc::new(AtomicUsize::new(0));
+        let total = options.total_steps();
+
+        let handle = {
+            let progress = Arc::clone(&progress);
+            let path = path.clone();
+            thread::spawn(move || {
+                let file = File::create(&path).map_err(|e| BitmapError::Image(e.to_string()))?;
+                let mut writer = BufWriter::new(file);
+                write_gif(&game, &mut writer, &options, &progress)?;
+                writer
+                    .flush()
+                    .map_err(|e| BitmapError::Image(e.to_string()))
+            })
+        };
+
+        Ok(Self {
+            path,
+            progress,
+            total,
+            handle: Some(handle),
+        })
+    }
+
+    /// Fraction of the generations simulated so far.
+    pub fn progress(&self) -> f32 {
+        if self.total == 0 {
+            return 1.0;
+        }
+        self.progress.load(Ordering::Relaxed) as f32 / self.total as f32
+    }
+
+    /// Returns the outcome once the export thread has finished.
+    pub fn try_finish(&mut self) -> Option<Result<(), BitmapError>> {
+        if !self.handle.as_ref()?.is_finished() {
+            return None;
+        }
+
+        let handle = self.handle.take()?;
+        Some(
+            handle
+                .join()
+                .unwrap_or_else(|_| Err(BitmapError::Image("export thread panicked".into()))),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+    use image::{AnimationDecoder, codecs::gif::GifDecoder};
+    use std::io::Cursor;
+
+    fn blinker_game() -> Game {
+        let mut grid = Grid::new(10, 10);
+        for x in 4..7 {
+            let _ = grid.set_cell_at_coord((x, 5), true);
+        }
+        Game::new(grid)
+    }
+
+    fn decode_frames(bytes: Vec<u8>) -> Vec<RgbaImage> {
+        GifDecoder::new(Cursor::new(bytes))
+            .unwrap()
+            .into_frames()
+            .map(|f| f.unwrap().into_buffer())
+            .collect()
+    }
+
+    #[test]
+    fn test_writing_one_frame_per_generation() {
+        let options = GifOptions {
+            generations: 3,
+            crop: false,
+            style: ImageStyle {
+                cell_size: 2,
+                ..ImageStyle::default()
+            },
+            ..GifOptions::default()
+        };
+        let progress = AtomicUsize::new(0);
+        let mut bytes = Vec::new();
+
+        write_gif(&blinker_game(), &mut bytes, &options, &progress).unwrap();
+        let frames = decode_frames(bytes);
+
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[0].dimensions(), (20, 20));
+        assert_eq!(progress.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn test_cropping_to_run_bounds() {
+        let options = GifOptions {
+            generations: 2,
+            style: ImageStyle {
+                cell_size: 1,
+                ..ImageStyle::default()
+            },
+            ..GifOptions::default()
+        };
+        let progress = AtomicUsize::new(0);
+        let mut bytes = Vec::new();
+
+        write_gif(&blinker_game(), &mut bytes, &options, &progress).unwrap();
+        let frames = decode_frames(bytes);
+
+        assert_eq!(frames[0].dimensions(), (3, 3));
+        assert_eq!(progress.load(Ordering::Relaxed), options.total_steps());
+    }
+
+    #[test]
+    fn test_export_leaves_original_game_untouched() {
+        let game = blinker_game();
+        let before = game.grid.live_cells().collect::<Vec<_>>();
+        let progress = AtomicUsize::new(0);
+
+        write_gif(&game, Vec::new(), &GifOptions::default(), &progress).unwrap();
+
+        assert_eq!(game.grid.live_cells().collect::<Vec<_>>(), before);
+    }
+
+    #[test]
+    fn test_oversized_frames_are_refused() {
+        let options = GifOptions {
+            generations: 1,
+            crop: false,
+            style: ImageStyle {
+                cell_size: 32,
+                ..ImageStyle::default()
+            },
+            ..GifOptions::default()
+        };
+        let cropped = GifOptions {
+            crop: true,
+            ..options.clone()
+        };
+        let mut wide = Grid::new(500, 300);
+        let _ = wide.set_cell_at_coord((0, 0), true);
+        let _ = wide.set_cell_at_coord((499, 299), true);
+        let mut bytes = Vec::new();
+
+        let started = GifExport::start(Game::new(wide.clone()), PathBuf::new(), options);
+        let written = write_gif(&Game::new(wide), &mut bytes, &cropped, &AtomicUsize::new(0));
+
+        assert!(matches!(started, Err(BitmapError::TooLarge { .. })));
+        assert!(matches!(written, Err(BitmapError::TooLarge { .. })));
+        assert!(bytes.is_empty());
+    }
+}
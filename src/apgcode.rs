@@ -0,0 +1,237 @@
+use std::fmt::Display;
+
+use crate::pattern::{Pattern, Transform};
+
+/// Longest period searched for when classifying an object.
+pub const MAX_PERIOD: usize = 64;
+
+const DIGITS: &[u8; 32] = b"0123456789abcdefghijklmnopqrstuv";
+const RUN_DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+const STRIP_HEIGHT: i64 = 5;
+
+/// Computes the Catagolue apgcode of a still life, oscillator or spaceship.
+/// Every phase and orientation is encoded and the shortest, then
+/// lexicographically smallest, representation is kept.
+pub fn encode(pattern: &Pattern) -> Result<String, ApgcodeError> {
+    if pattern.is_empty() {
+        return Err(ApgcodeError::Empty);
+    }
+
+    let mut phases = vec![pattern.clone()];
+    let mut current = pattern.step();
+    let start = pattern.normalized();
+
+    while current.normalized() != start {
+        if phases.len() == MAX_PERIOD || current.is_empty() {
+            return Err(ApgcodeError::NotPeriodic);
+        }
+        phases.push(current.clone());
+        current = current.step();
+    }
+
+    let period = phases.len();
+    let moved = current.bounds() != pattern.bounds();
+    let prefix = match (period, moved) {
+        (_, true) => format!("xq{period}"),
+        (1, false) => format!("xs{}", pattern.population()),
+        (_, false) => format!("xp{period}"),
+    };
+
+    let wechsler = phases
+        .iter()
+        .flat_map(|phase| Transform::ALL.map(|t| wechsler(&phase.transformed(t))))
+        .min_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)))
+        .expect("encode: at least one phase");
+
+    Ok(format!("{prefix}_{wechsler}"))
+}
+
+/// Rebuilds the phase described by an apgcode, with its top-left at (0, 0).
+pub fn decode(code: &str) -> Result<Pattern, ApgcodeError> {
+    let invalid = || ApgcodeError::Invalid(code.to_string());
+
+    let (prefix, body) = code.split_once('_').ok_or_else(invalid)?;
+    let kind = prefix.strip_prefix('x').ok_or_else(invalid)?;
+    if !kind.starts_with(['s', 'p', 'q']) || kind[1..].parse::<usize>().is_err() {
+        return Err(invalid());
+    }
+
+    let mut cells = Vec::new();
+    let (mut x, mut strip) = (0i64, 0i64);
+    let mut chars = body.bytes();
+
+    while let Some(c) = chars.next() {
+        match c {
+            b'w' => x += 2,
+            b'x' => x += 3,
+            b'y' => {
+                let run = chars
+                    .next()
+                    .and_then(|n| RUN_DIGITS.iter().position(|&d| d == n));
+                x += 4 + run.ok_or_else(invalid)? as i64;
+            }
+            b'z' => {
+                x = 0;
+                strip += 1;
+            }
+            _ => {
+                let value = DIGITS.iter().position(|&d| d == c).ok_or_else(invalid)?;
+                for row in 0..STRIP_HEIGHT {
+                    if value & (1 << row) != 0 {
+                        cells.push((x, strip * STRIP_HEIGHT + row));
+                    }
+                }
+                x += 1;
+            }
+        }
+    }
+
+    Ok(Pattern::new(cells))
+}
+
+/// Extended Wechsler encoding of a single phase in a fixed orientation.
+fn wechsler(pattern: &Pattern) -> String {
+    let pattern = pattern.normalized();
+    let Some(b) = pattern.bounds() else {
+        return String::new();
+    };
+
+    let strips = b.height().div_ceil(STRIP_HEIGHT as usize);
+    let mut columns = vec![vec![0usize; b.width()]; strips];
+    for &(x, y) in pattern.cells() {
+        columns[(y / STRIP_HEIGHT) as usize][x as usize] |= 1 << (y % STRIP_HEIGHT);
+    }
+
+    let mut out = String::new();
+    for (i, strip) in columns.iter().enumerate() {
+        if i > 0 {
+            out.push('z');
+        }
+
+        let end = strip.iter().rposition(|&v| v != 0).map_or(0, |p| p + 1);
+        let mut zeros = 0;
+        for &value in &strip[..end] {
+            if value == 0 {
+                zeros += 1;
+                continue;
+            }
+            push_zero_run(&mut out, zeros);
+            zeros = 0;
+            out.push(DIGITS[value] as char);
+        }
+    }
+    out
+}
+
+fn push_zero_run(out: &mut String, mut zeros: usize) {
+    while zeros >= 4 {
+        let run = zeros.min(4 + RUN_DIGITS.len() - 1);
+        out.push('y');
+        out.push(RUN_DIGITS[run - 4] as char);
+        zeros -= run;
+    }
+
+    match zeros {
+        1 => out.push('0'),
+        2 => out.push('w'),
+        3 => out.push('x'),
+        _ => {}
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ApgcodeError {
+    Empty,
+    NotPeriodic,
+    Invalid(String),
+}
+
+impl Display for ApgcodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ApgcodeError::Empty => write!(f, "there are no live cells"),
+            ApgcodeError::NotPeriodic => {
+                write!(
+                    f,
+                    "not a still life, oscillator or spaceship (period > {MAX_PERIOD})"
+                )
+            }
+            ApgcodeError::Invalid(code) => write!(f, "'{code}' is not a valid apgcode"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pattern(rows: &[&str]) -> Pattern {
+        let cells = rows
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.chars()
+                    .enumerate()
+                    .filter(|&(_, c)| c == '*')
+                    .map(move |(x, _)| (x as i64, y as i64))
+            })
+            .collect();
+        Pattern::new(cells)
+    }
+
+    #[test]
+    fn test_encoding_still_lifes() {
+        assert_eq!(encode(&pattern(&["**", "**"])).unwrap(), "xs4_33");
+        assert_eq!(
+            encode(&pattern(&[".**.", "*..*", ".**."])).unwrap(),
+            "xs6_696"
+        );
+    }
+
+    #[test]
+    fn test_encoding_oscillator_picks_canonical_phase() {
+        assert_eq!(encode(&pattern(&["***"])).unwrap(), "xp2_7");
+    }
+
+    #[test]
+    fn test_encoding_spaceships() {
+        let glider = pattern(&[".*.", "..*", "***"]);
+        let lwss = pattern(&[".*..*", "*....", "*...*", "****."]);
+
+        assert_eq!(encode(&glider).unwrap(), "xq4_153");
+        assert_eq!(encode(&lwss).unwrap(), "xq4_6frc");
+    }
+
+    #[test]
+    fn test_encoding_non_periodic_pattern() {
+        let r_pentomino = pattern(&[".**", "**.", ".*."]);
+
+        assert_eq!(encode(&r_pentomino), Err(ApgcodeError::NotPeriodic));
+        assert_eq!(encode(&Pattern::default()), Err(ApgcodeError::Empty));
+    }
+
+    #[test]
+    fn test_encoding_zero_runs_and_strips() {
+        let far_apart = pattern(&["*.....*", ".", ".", ".", ".", "*"]);
+
+        assert_eq!(wechsler(&far_apart), "1y11z1");
+        assert_eq!(decode("xs3_1y11z1").unwrap(), far_apart);
+    }
+
+    #[test]
+    fn test_decoding_round_trips() {
+        let pulsar = "xp3_co9nas0san9oczgoldlo0oldlogz1047210127401";
+
+        let decoded = decode(pulsar).unwrap();
+
+        assert_eq!(decoded.population(), 72);
+        assert_eq!(encode(&decoded).unwrap(), pulsar);
+    }
+
+    #[test]
+    fn test_decoding_rejects_invalid_codes() {
+        assert!(decode("ov_s23").is_err());
+        assert!(decode("xs4_3#").is_err());
+        assert!(decode("xs4").is_err());
+    }
+}
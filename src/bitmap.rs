@@ -0,0 +1,306 @@
+use std::{fmt::Display, path::Path};
+
+use image::{GrayImage, ImageFormat, Rgb, RgbImage};
+
+use crate::{
+    grid::Grid,
+    pattern::{Pattern, PatternError},
+};
+
+/// Most pixels a rendered image may have, about 48 MB of RGB.
+pub const MAX_PIXELS: u64 = 1 << 24;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImageStyle {
+    pub cell_size: u32,
+    pub alive: [u8; 3],
+    pub dead: [u8; 3],
+    pub grid_lines: Option<[u8; 3]>,
+}
+
+impl Default for ImageStyle {
+    fn default() -> Self {
+        Self {
+            cell_size: 8,
+            alive: [20, 20, 20],
+            dead: [255, 255, 255],
+            grid_lines: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImportOptions {
+    /// Pixels darker than this become live cells.
+    pub threshold: u8,
+    pub dither: bool,
+    pub invert: bool,
+    /// Side of the square of pixels averaged into one cell.
+    pub pixels_per_cell: u32,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        Self {
+            threshold: 128,
+            dither: false,
+            invert: false,
+            pixels_per_cell: 1,
+        }
+    }
+}
+
+/// Pixel size of a `width` by `height` grid drawn with `style`, refusing
+/// anything over [`MAX_PIXELS`].
+pub fn image_size(
+    (width, height): (usize, usize),
+    style: &ImageStyle,
+) -> Result<(u32, u32), BitmapError> {
+    let size = u64::from(style.cell_size.max(1));
+    let line = u64::from(style.grid_lines.is_some());
+    let width = (width as u64).saturating_mul(size).saturating_add(line);
+    let height = (height as u64).saturating_mul(size).saturating_add(line);
+
+    if width.saturating_mul(height) > MAX_PIXELS {
+        return Err(BitmapError::TooLarge { width, height });
+    }
+    Ok((width as u32, height as u32))
+}
+
+/// Draws every cell as a `cell_size` square. Grid lines, when enabled, are
+/// drawn over the top and left edge of each cell plus one closing line.
+pub fn render(grid: &Grid, style: &ImageStyle) -> Result<RgbImage, BitmapError> {
+    let size = style.cell_size.max(1);
+    let (width, height) = image_size((grid.width(), grid.height()), style)?;
+    let mut image = RgbImage::from_pixel(width, height, Rgb(style.dead));
+
+    for (x, y) in grid.live_cells() {
+        let (left, top) = (x as u32 * size, y as u32 * size);
+        for py in top..top + size {
+            for px in left..left + size {
+                image.put_pixel(px, py, Rgb(style.alive));
+            }
+        }
+    }
+
+    if let Some(color) = style.grid_lines {
+        for x in (0..width).step_by(size as usize) {
+            for y in 0..height {
+                image.put_pixel(x, y, Rgb(color));
+            }
+        }
+        for y in (0..height).step_by(size as usize) {
+            for x in 0..width {
+                image.put_pixel(x, y, Rgb(color));
+            }
+        }
+    }
+
+    Ok(image)
+}
+
+pub fn export_png(path: &Path, grid: &Grid, style: &ImageStyle) -> Result<(), BitmapError> {
+    render(grid, style)?
+        .save_with_format(path, ImageFormat::Png)
+        .map_err(|e| BitmapError::Image(e.to_string()))
+}
+
+/// Reads a PNG or BMP and turns dark pixels into live cells.
+pub fn import_image(
+    path: &Path,
+    options: &ImportOptions,
+    max_size: (usize, usize),
+) -> Result<Pattern, BitmapError> {
+    let image = image::open(path)
+        .map_err(|e| BitmapError::Image(e.to_string()))?
+        .to_luma8();
+
+    let pattern = threshold(&image, options);
+    pattern.check_fits(max_size.0, max_size.1)?;
+    Ok(pattern)
+}
+
+pub fn threshold(image: &GrayImage, options: &ImportOptions) -> Pattern {
+    let block = options.pixels_per_cell.max(1);
+    let width = image.width().div_ceil(block) as usize;
+    let height = image.height().div_ceil(block) as usize;
+
+    let mut levels = vec![0.0f32; width * height];
+    for (i, level) in levels.iter_mut().enumerate() {
+        let (cx, cy) = ((i % width) as u32 * block, (i / width) as u32 * block);
+        let xs = cx..(cx + block).min(image.width());
+        let ys = cy..(cy + block).min(image.height());
+        let count = (xs.len() * ys.len()) as f32;
+        let sum: f32 = ys
+            .flat_map(|y| xs.clone().map(move |x| image.get_pixel(x, y)[0] as f32))
+            .sum();
+        *level = sum / count;
+    }
+
+    let limit = options.threshold as f32;
+    let mut cells = Vec::new();
+    for y in 0..height {
+        for x in 0..width {
+            let level = levels[y * width + x];
+            let dark = level < limit;
+            if dark != options.invert {
+                cells.push((x as i64, y as i64));
+            }
+
+            if options.dither {
+                let error = level - if dark { 0.0 } else { 255.0 };
+                diffuse(&mut levels, width, height, (x, y), error);
+            }
+        }
+    }
+
+    Pattern::new(cells)
+}
+
+/// Floyd-Steinberg error diffusion onto the unvisited neighbours.
+fn diffuse(levels: &mut [f32], width: usize, height: usize, (x, y): (usize, usize), error: f32) {
+    let targets = [(1, 0, 7.0), (-1, 1, 3.0), (0, 1, 5.0), (1, 1, 1.0)];
+
+    for (dx, dy, weight) in targets {
+        let (nx, ny) = (x as i64 + dx, y as i64 + dy);
+        if nx < 0 || nx >= width as i64 || ny >= height as i64 {
+            continue;
+        }
+        levels[ny as usize * width + nx as usize] += error * weight / 16.0;
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BitmapError {
+    Image(String),
+    Pattern(PatternError),
+    /// The image would have more than [`MAX_PIXELS`].
+    TooLarge {
+        width: u64,
+        height: u64,
+    },
+}
+
+impl From<PatternError> for BitmapError {
+    fn from(e: PatternError) -> Self {
+        BitmapError::Pattern(e)
+    }
+}
+
+impl Display for BitmapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BitmapError::Image(message) => write!(f, "{message}"),
+            BitmapError::Pattern(e) => write!(f, "{e}"),
+            BitmapError::TooLarge { width, height } => write!(
+                f,
+                "image would be {width}x{height} pixels, more than the {MAX_PIXELS} allowed"
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Luma;
+
+    #[test]
+    fn test_rendering_cells() {
+        let mut grid = Grid::new(2, 1);
+        let _ = grid.set_cell_at_coord((1, 0), true);
+        let style = ImageStyle {
+            cell_size: 3,
+            ..ImageStyle::default()
+        };
+
+        let image = render(&grid, &style).unwrap();
+
+        assert_eq!(image.dimensions(), (6, 3));
+        assert_eq!(image.get_pixel(1, 1), &Rgb(style.dead));
+        assert_eq!(image.get_pixel(4, 1), &Rgb(style.alive));
+    }
+
+    #[test]
+    fn test_rendering_grid_lines() {
+        let grid = Grid::new(2, 2);
+        let style = ImageStyle {
+            cell_size: 4,
+            grid_lines: Some([255, 0, 0]),
+            ..ImageStyle::default()
+        };
+
+        let image = render(&grid, &style).unwrap();
+
+        assert_eq!(image.dimensions(), (9, 9));
+        assert_eq!(image.get_pixel(8, 2), &Rgb([255, 0, 0]));
+        assert_eq!(image.get_pixel(2, 4), &Rgb([255, 0, 0]));
+        assert_eq!(image.get_pixel(2, 2), &Rgb(style.dead));
+    }
+
+    #[test]
+    fn test_rendering_rejects_oversized_images() {
+        let grid = Grid::new(500, 300);
+        let style = ImageStyle {
+            cell_size: 64,
+            grid_lines: Some([0, 0, 0]),
+            ..ImageStyle::default()
+        };
+
+        let result = render(&grid, &style);
+
+        assert_eq!(
+            result,
+            Err(BitmapError::TooLarge {
+                width: 32001,
+                height: 19201
+            })
+        );
+        assert!(image_size((500, 300), &ImageStyle::default()).is_ok());
+    }
+
+    #[test]
+    fn test_thresholding_dark_pixels() {
+        let image = GrayImage::from_fn(3, 2, |x, _| Luma([if x == 1 { 10 } else { 240 }]));
+
+        let pattern = threshold(&image, &ImportOptions::default());
+        let inverted = threshold(
+            &image,
+            &ImportOptions {
+                invert: true,
+                ..ImportOptions::default()
+            },
+        );
+
+        assert_eq!(pattern.cells(), &[(1, 0), (1, 1)]);
+        assert_eq!(inverted.population(), 4);
+    }
+
+    #[test]
+    fn test_thresholding_averages_pixel_blocks() {
+        let image = GrayImage::from_fn(4, 2, |x, y| Luma([if x < 2 && y == 0 { 0 } else { 255 }]));
+        let options = ImportOptions {
+            pixels_per_cell: 2,
+            ..ImportOptions::default()
+        };
+
+        let pattern = threshold(&image, &options);
+
+        assert_eq!(pattern.cells(), &[(0, 0)]);
+    }
+
+    #[test]
+    fn test_dithering_mid_grey() {
+        let image = GrayImage::from_pixel(10, 10, Luma([128]));
+        let options = ImportOptions {
+            dither: true,
+            ..ImportOptions::default()
+        };
+
+        let plain = threshold(&image, &ImportOptions::default());
+        let dithered = threshold(&image, &options);
+
+        assert_eq!(plain.population(), 0);
+        assert!((40..=60).contains(&dithered.population()));
+    }
+}
@@ -0,0 +1,297 @@
+use crate::pattern::{Pattern, Transform};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Tool {
+    /// Flips the clicked cell.
+    #[default]
+    Toggle,
+    Brush,
+    Line,
+    Rectangle,
+    Ellipse,
+}
+
+impl Tool {
+    pub const ALL: [Tool; 5] = [
+        Tool::Toggle,
+        Tool::Brush,
+        Tool::Line,
+        Tool::Rectangle,
+        Tool::Ellipse,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Tool::Toggle => "Toggle",
+            Tool::Brush => "Brush",
+            Tool::Line => "Line",
+            Tool::Rectangle => "Rectangle",
+            Tool::Ellipse => "Ellipse",
+        }
+    }
+
+    /// Tools drawn by dragging from one corner to another.
+    pub fn is_shape(self) -> bool {
+        matches!(self, Tool::Line | Tool::Rectangle | Tool::Ellipse)
+    }
+}
+
+/// Copies made of every edit while drawing, mirrored or turned about a centre.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum DrawSymmetry {
+    #[default]
+    None,
+    /// Mirrored left to right across a vertical axis.
+    Horizontal,
+    /// Mirrored top to bottom across a horizontal axis.
+    Vertical,
+    Both,
+    /// Four copies a quarter turn apart.
+    Rotational,
+    /// Every rotation and reflection of the square.
+    Eightfold,
+}
+
+impl DrawSymmetry {
+    pub const ALL: [DrawSymmetry; 6] = [
+        DrawSymmetry::None,
+        DrawSymmetry::Horizontal,
+        DrawSymmetry::Vertical,
+        DrawSymmetry::Both,
+        DrawSymmetry::Rotational,
+        DrawSymmetry::Eightfold,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            DrawSymmetry::None => "None",
+            DrawSymmetry::Horizontal => "Horizontal",
+            DrawSymmetry::Vertical => "Vertical",
+            DrawSymmetry::Both => "Both",
+            DrawSymmetry::Rotational => "90° rotational",
+            DrawSymmetry::Eightfold => "8-fold",
+        }
+    }
+
+    pub fn transforms(self) -> &'static [Transform] {
+        match self {
+            DrawSymmetry::None => &[Transform::Identity],
+            DrawSymmetry::Horizontal => &[Transform::Identity, Transform::FlipHorizontal],
+            DrawSymmetry::Vertical => &[Transform::Identity, Transform::FlipVertical],
+            DrawSymmetry::Both => &[
+                Transform::Identity,
+                Transform::FlipHorizontal,
+                Transform::FlipVertical,
+                Transform::Rotate180,
+            ],
+            DrawSymmetry::Rotational => &[
+                Transform::Identity,
+                Transform::Rotate90,
+                Transform::Rotate180,
+                Transform::Rotate270,
+            ],
+            DrawSymmetry::Eightfold => &Transform::ALL,
+        }
+    }
+}
+
+/// Adds the symmetric copies of every cell about `centre`, given in half
+/// cells so the centre can sit between cells. Copies that would land between
+/// cells, as quarter turns can about such a centre, are left out.
+pub fn symmetric(pattern: &Pattern, symmetry: DrawSymmetry, centre: (i64, i64)) -> Pattern {
+    if symmetry == DrawSymmetry::None {
+        return pattern.clone();
+    }
+
+    let (cx, cy) = centre;
+    let cells = pattern
+        .cells()
+        .iter()
+        .flat_map(|&(x, y)| {
+            symmetry.transforms().iter().filter_map(move |t| {
+                let (tx, ty) = t.apply((2 * x - cx, 2 * y - cy));
+                let (dx, dy) = (tx + cx, ty + cy);
+                (dx % 2 == 0 && dy % 2 == 0).then_some((dx / 2, dy / 2))
+            })
+        })
+        .collect();
+    Pattern::new(cells)
+}
+
+/// Cells of a straight line between two cells, with no gaps (Bresenham).
+pub fn line(from: (i64, i64), to: (i64, i64)) -> Pattern {
+    let (mut x, mut y) = from;
+    let dx = (to.0 - x).abs();
+    let dy = -(to.1 - y).abs();
+    let sx = if x < to.0 { 1 } else { -1 };
+    let sy = if y < to.1 { 1 } else { -1 };
+    let mut err = dx + dy;
+
+    let mut cells = vec![(x, y)];
+    while (x, y) != to {
+        let e2 = 2 * err;
+        if e2 >= dy {
+            err += dy;
+            x += sx;
+        }
+        if e2 <= dx {
+            err += dx;
+            y += sy;
+        }
+        cells.push((x, y));
+    }
+    Pattern::new(cells)
+}
+
+pub fn rectangle(a: (i64, i64), b: (i64, i64), filled: bool) -> Pattern {
+    let (left, right) = (a.0.min(b.0), a.0.max(b.0));
+    let (top, bottom) = (a.1.min(b.1), a.1.max(b.1));
+
+    let cells = (top..=bottom)
+        .flat_map(|y| (left..=right).map(move |x| (x, y)))
+        .filter(|&(x, y)| filled || x == left || x == right || y == top || y == bottom)
+        .collect();
+    Pattern::new(cells)
+}
+
+/// Ellipse inscribed in the box with corners `a` and `b`.
+pub fn ellipse(a: (i64, i64), b: (i64, i64), filled: bool) -> Pattern {
+    let (left, right) = (a.0.min(b.0), a.0.max(b.0));
+    let (top, bottom) = (a.1.min(b.1), a.1.max(b.1));
+    let cx = (left + right) as f64 / 2.0;
+    let cy = (top + bottom) as f64 / 2.0;
+    // Half a cell of slack so thin ellipses still cover their box.
+    let rx = (right - left) as f64 / 2.0 + 0.5;
+    let ry = (bottom - top) as f64 / 2.0 + 0.5;
+
+    let inside = |x: i64, y: i64| {
+        let nx = (x as f64 - cx) / rx;
+        let ny = (y as f64 - cy) / ry;
+        nx * nx + ny * ny <= 1.0
+    };
+
+    let cells = (top..=bottom)
+        .flat_map(|y| (left..=right).map(move |x| (x, y)))
+        .filter(|&(x, y)| inside(x, y))
+        .filter(|&(x, y)| {
+            filled
+                || !inside(x - 1, y)
+                || !inside(x + 1, y)
+                || !inside(x, y - 1)
+                || !inside(x, y + 1)
+        })
+        .collect();
+    Pattern::new(cells)
+}
+
+/// Round brush footprint of the given diameter, centred on (0, 0).
+pub fn brush(size: usize) -> Pattern {
+    let size = size.max(1) as i64;
+    let (low, high) = (-(size - 1) / 2, size / 2);
+    let radius = size as f64 / 2.0;
+    let centre = (low + high) as f64 / 2.0;
+
+    let cells = (low..=high)
+        .flat_map(|y| (low..=high).map(move |x| (x, y)))
+        .filter(|&(x, y)| {
+            let (dx, dy) = (x as f64 - centre, y as f64 - centre);
+            size <= 2 || dx * dx + dy * dy <= radius * radius
+        })
+        .collect();
+    Pattern::new(cells)
+}
+
+/// Thickens every cell of `shape` to the brush footprint.
+pub fn stroke(shape: &Pattern, brush: &Pattern) -> Pattern {
+    let cells = shape
+        .cells()
+        .iter()
+        .flat_map(|&(x, y)| brush.cells().iter().map(move |&(bx, by)| (x + bx, y + by)))
+        .collect();
+    Pattern::new(cells)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_has_no_gaps() {
+        let steep = line((0, 0), (2, 5));
+        let backwards = line((3, 1), (0, 0));
+
+        assert_eq!(steep.population(), 6);
+        assert!(
+            steep
+                .cells()
+                .windows(2)
+                .all(|w| (w[0].0 - w[1].0).abs() <= 1 && (w[0].1 - w[1].1).abs() == 1)
+        );
+        assert_eq!(backwards.cells(), &[(0, 0), (1, 0), (2, 1), (3, 1)]);
+    }
+
+    #[test]
+    fn test_rectangle_outline_and_fill() {
+        let outline = rectangle((3, 2), (0, 0), false);
+        let filled = rectangle((0, 0), (3, 2), true);
+
+        assert_eq!(outline.population(), 10);
+        assert!(!outline.cells().contains(&(1, 1)));
+        assert_eq!(filled.population(), 12);
+    }
+
+    #[test]
+    fn test_ellipse_fits_its_box() {
+        let filled = ellipse((0, 0), (6, 4), true);
+        let outline = ellipse((0, 0), (6, 4), false);
+
+        let b = filled.bounds().unwrap();
+        assert_eq!((b.min_x, b.min_y, b.max_x, b.max_y), (0, 0, 6, 4));
+        assert!(filled.cells().contains(&(3, 2)));
+        assert!(!outline.cells().contains(&(3, 2)));
+        assert!(outline.cells().iter().all(|c| filled.cells().contains(c)));
+    }
+
+    #[test]
+    fn test_brush_sizes() {
+        assert_eq!(brush(1).cells(), &[(0, 0)]);
+        assert_eq!(brush(2).population(), 4);
+        assert_eq!(brush(3).population(), 9);
+        assert!(!brush(5).cells().contains(&(-2, -2)));
+        assert!(brush(5).cells().contains(&(0, 0)));
+    }
+
+    #[test]
+    fn test_symmetric_copies_about_centre() {
+        let cell = Pattern::new(vec![(1, 0)]);
+
+        let mirrored = symmetric(&cell, DrawSymmetry::Horizontal, (5, 0));
+        let both = symmetric(&cell, DrawSymmetry::Both, (4, 4));
+        let eightfold = symmetric(&cell, DrawSymmetry::Eightfold, (4, 4));
+
+        assert_eq!(mirrored.cells(), &[(1, 0), (4, 0)]);
+        assert_eq!(both.cells(), &[(1, 0), (3, 0), (1, 4), (3, 4)]);
+        assert_eq!(eightfold.population(), 8);
+    }
+
+    #[test]
+    fn test_rotational_copies_skip_cells_between_cells() {
+        let cell = Pattern::new(vec![(0, 0)]);
+
+        let whole = symmetric(&cell, DrawSymmetry::Rotational, (2, 2));
+        let between = symmetric(&cell, DrawSymmetry::Rotational, (3, 2));
+
+        assert_eq!(whole.cells(), &[(0, 0), (2, 0), (0, 2), (2, 2)]);
+        assert_eq!(between.cells(), &[(0, 0), (3, 2)]);
+    }
+
+    #[test]
+    fn test_stroke_thickens_shape() {
+        let thick = stroke(&line((0, 0), (3, 0)), &brush(3));
+
+        assert_eq!(
+            thick.bounds().map(|b| (b.width(), b.height())),
+            Some((6, 3))
+        );
+    }
+}
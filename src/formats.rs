@@ -0,0 +1,232 @@
+use std::{fmt::Display, fs, path::Path};
+
+use crate::pattern::{Pattern, PatternError};
+
+pub mod life105;
+pub mod life106;
+pub mod macrocell;
+pub mod plaintext;
+pub mod rle;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum PatternFormat {
+    Life105,
+    #[default]
+    Life106,
+    Macrocell,
+    Rle,
+    Plaintext,
+}
+
+impl PatternFormat {
+    pub const ALL: [PatternFormat; 5] = [
+        PatternFormat::Life105,
+        PatternFormat::Life106,
+        PatternFormat::Macrocell,
+        PatternFormat::Rle,
+        PatternFormat::Plaintext,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            PatternFormat::Life105 => "Life 1.05",
+            PatternFormat::Life106 => "Life 1.06",
+            PatternFormat::Macrocell => "Macrocell",
+            PatternFormat::Rle => "RLE",
+            PatternFormat::Plaintext => "Plaintext",
+        }
+    }
+
+    /// Guesses the format from the file header, falling back to the extension.
+    pub fn detect(path: &Path, contents: &str) -> Option<Self> {
+        Self::detect_contents(contents).or_else(|| Self::from_extension(path))
+    }
+
+    /// The format a file's extension suggests, if any.
+    pub fn from_extension(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "lif" | "life" => Some(PatternFormat::Life106),
+            "mc" => Some(PatternFormat::Macrocell),
+            "rle" => Some(PatternFormat::Rle),
+            "cells" => Some(PatternFormat::Plaintext),
+            _ => None,
+        }
+    }
+
+    /// Guesses the format from the text alone, as for clipboard contents.
+    pub fn detect_contents(contents: &str) -> Option<Self> {
+        let header = contents.trim_start().lines().next().unwrap_or("").trim();
+        if header.starts_with(life105::HEADER) {
+            return Some(PatternFormat::Life105);
+        }
+        if header.starts_with(life106::HEADER) {
+            return Some(PatternFormat::Life106);
+        }
+        if header.starts_with(macrocell::HEADER) {
+            return Some(PatternFormat::Macrocell);
+        }
+        if rle::has_header(contents) {
+            return Some(PatternFormat::Rle);
+        }
+        if plaintext::looks_like(contents) {
+            return Some(PatternFormat::Plaintext);
+        }
+        None
+    }
+
+    /// Parses a pattern, rejecting it if its bounding box exceeds `max_size`.
+    pub fn parse(self, input: &str, max_size: (usize, usize)) -> Result<Pattern, FormatError> {
+        let pattern = match self {
+            PatternFormat::Life105 => life105::parse(input)?,
+            PatternFormat::Life106 => life106::parse(input)?,
+            PatternFormat::Macrocell => macrocell::parse(input, max_size)?,
+            PatternFormat::Rle => rle::parse(input, max_size)?,
+            PatternFormat::Plaintext => plaintext::parse(input)?,
+        };
+
+        pattern.check_fits(max_size.0, max_size.1)?;
+        Ok(pattern)
+    }
+
+    pub fn write(self, pattern: &Pattern) -> String {
+        match self {
+            PatternFormat::Life105 => life105::write(pattern),
+            PatternFormat::Life106 => life106::write(pattern),
+            PatternFormat::Macrocell => macrocell::write(pattern),
+            PatternFormat::Rle => rle::write(pattern),
+            PatternFormat::Plaintext => plaintext::write(pattern),
+        }
+    }
+}
+
+pub fn read_pattern_file(path: &Path, max_size: (usize, usize)) -> Result<Pattern, FormatError> {
+    let contents = fs::read_to_string(path).map_err(|e| FormatError::Io(e.to_string()))?;
+    let format = PatternFormat::detect(path, &contents).ok_or(FormatError::UnknownFormat)?;
+    format.parse(&contents, max_size)
+}
+
+/// Parses pasted text in whichever format it looks like.
+pub fn parse_text(text: &str, max_size: (usize, usize)) -> Result<Pattern, FormatError> {
+    let format = PatternFormat::detect_contents(text).ok_or(FormatError::UnknownFormat)?;
+    format.parse(text, max_size)
+}
+
+pub fn write_pattern_file(
+    path: &Path,
+    format: PatternFormat,
+    pattern: &Pattern,
+) -> Result<(), FormatError> {
+    fs::write(path, format.write(pattern)).map_err(|e| FormatError::Io(e.to_string()))
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FormatError {
+    Io(String),
+    UnknownFormat,
+    Parse { line: usize, message: String },
+    UnsupportedRule(String),
+    Pattern(PatternError),
+}
+
+impl FormatError {
+    fn parse(line: usize, message: impl Into<String>) -> Self {
+        FormatError::Parse {
+            line: line + 1,
+            message: message.into(),
+        }
+    }
+
+    fn out_of_range(line: usize) -> Self {
+        FormatError::parse(line, "coordinates out of range")
+    }
+}
+
+/// Largest coordinate accepted from a file, leaving room to measure and move
+/// patterns without overflowing.
+const MAX_COORD: u64 = 1 << 60;
+
+fn in_range(coord: i64) -> bool {
+    coord.unsigned_abs() <= MAX_COORD
+}
+
+impl Display for FormatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FormatError::Io(message) => write!(f, "{message}"),
+            FormatError::UnknownFormat => write!(f, "unrecognised pattern format"),
+            FormatError::Parse { line, message } => write!(f, "line {line}: {message}"),
+            FormatError::UnsupportedRule(rule) => write!(f, "unsupported rule '{rule}'"),
+            FormatError::Pattern(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<PatternError> for FormatError {
+    fn from(e: PatternError) -> Self {
+        FormatError::Pattern(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detecting_format_from_header() {
+        let path = Path::new("glider.lif");
+
+        assert_eq!(
+            PatternFormat::detect(path, "#Life 1.05\n#P 0 0\n*"),
+            Some(PatternFormat::Life105)
+        );
+        assert_eq!(
+            PatternFormat::detect(path, "#Life 1.06\n0 0"),
+            Some(PatternFormat::Life106)
+        );
+        assert_eq!(
+            PatternFormat::detect(path, "[M2] (golly 4.2)\n"),
+            Some(PatternFormat::Macrocell)
+        );
+    }
+
+    #[test]
+    fn test_detecting_clipboard_text() {
+        assert_eq!(
+            PatternFormat::detect_contents("#N Glider\nx = 3, y = 3\nbo$2bo$3o!"),
+            Some(PatternFormat::Rle)
+        );
+        assert_eq!(
+            PatternFormat::detect_contents("!Name: Glider\n.O\n..O\nOOO\n"),
+            Some(PatternFormat::Plaintext)
+        );
+        assert_eq!(
+            PatternFormat::detect(Path::new("glider.rle"), "bo$2bo$3o!"),
+            Some(PatternFormat::Rle)
+        );
+        assert_eq!(PatternFormat::detect_contents("hello"), None);
+    }
+
+    #[test]
+    fn test_parsing_rejects_patterns_larger_than_limit() {
+        let result = PatternFormat::Life106.parse("#Life 1.06\n-3 0\n3 0\n", (5, 5));
+
+        assert_eq!(
+            result,
+            Err(FormatError::Pattern(PatternError::TooLarge {
+                width: 7,
+                height: 1,
+                grid_width: 5,
+                grid_height: 5,
+            }))
+        );
+    }
+
+    #[test]
+    fn test_detecting_unknown_format() {
+        assert_eq!(
+            PatternFormat::detect(Path::new("glider.txt"), "hello"),
+            None
+        );
+    }
+}
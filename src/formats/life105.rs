@@ -0,0 +1,135 @@
+use crate::pattern::Pattern;
+
+use super::{FormatError, in_range};
+
+pub const HEADER: &str = "#Life 1.05";
+
+const LIFE_RULE: &str = "23/3";
+
+pub fn parse(input: &str) -> Result<Pattern, FormatError> {
+    let mut cells = Vec::new();
+    let mut block: Option<(i64, i64)> = None;
+    let mut row = 0;
+
+    for (n, line) in input.lines().enumerate() {
+        let line = line.trim();
+
+        if let Some(rest) = line.strip_prefix("#P") {
+            let mut coords = rest.split_whitespace().map(str::parse::<i64>);
+            match (coords.next(), coords.next()) {
+                (Some(Ok(x)), Some(Ok(y))) if in_range(x) && in_range(y) => {
+                    block = Some((x, y));
+                }
+                (Some(Ok(_)), Some(Ok(_))) => return Err(FormatError::out_of_range(n)),
+                _ => return Err(FormatError::parse(n, "expected '#P x y'")),
+            }
+            row = 0;
+            continue;
+        }
+
+        if let Some(rule) = line.strip_prefix("#R") {
+            let rule = rule.trim();
+            if rule != LIFE_RULE {
+                return Err(FormatError::UnsupportedRule(rule.to_string()));
+            }
+            continue;
+        }
+
+        if line.starts_with('#') || (line.is_empty() && block.is_none()) {
+            continue;
+        }
+
+        let (bx, by) = *block.get_or_insert((0, 0));
+        for (x, c) in line.chars().enumerate() {
+            match c {
+                '*' => cells.push((bx + x as i64, by + row)),
+                '.' => {}
+                _ => return Err(FormatError::parse(n, format!("unexpected character '{c}'"))),
+            }
+        }
+        row += 1;
+    }
+
+    Ok(Pattern::new(cells))
+}
+
+/// Writes the pattern as a single `#P` block centred on the origin.
+pub fn write(pattern: &Pattern) -> String {
+    let mut out = format!("{HEADER}\n#N\n");
+    let pattern = pattern.centered();
+    let Some(b) = pattern.bounds() else {
+        return out;
+    };
+
+    out += &format!("#P {} {}\n", b.min_x, b.min_y);
+
+    let mut rows = vec![vec!['.'; b.width()]; b.height()];
+    for &(x, y) in pattern.cells() {
+        rows[(y - b.min_y) as usize][(x - b.min_x) as usize] = '*';
+    }
+
+    for row in rows {
+        let line: String = row.into_iter().collect();
+        match line.trim_end_matches('.') {
+            "" => out += ".",
+            trimmed => out += trimmed,
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parsing_blocks_with_negative_offsets() {
+        let input = "#Life 1.05\n#D glider\n#N\n#P -1 -1\n.*\n..*\n***\n";
+
+        let pattern = parse(input).unwrap();
+
+        assert_eq!(pattern.cells(), &[(0, -1), (1, 0), (-1, 1), (0, 1), (1, 1)]);
+    }
+
+    #[test]
+    fn test_parsing_multiple_blocks() {
+        let input = "#Life 1.05\n#P 0 0\n**\n#P 10 -3\n*\n.\n*\n";
+
+        let pattern = parse(input).unwrap();
+
+        assert_eq!(pattern.cells(), &[(10, -3), (10, -1), (0, 0), (1, 0)]);
+    }
+
+    #[test]
+    fn test_parsing_rejects_other_rules() {
+        let result = parse("#Life 1.05\n#R 23/36\n#P 0 0\n*\n");
+
+        assert_eq!(result, Err(FormatError::UnsupportedRule("23/36".into())));
+    }
+
+    #[test]
+    fn test_parsing_reports_bad_characters() {
+        let result = parse("#Life 1.05\n#P 0 0\n*o*\n");
+
+        assert_eq!(
+            result,
+            Err(FormatError::Parse {
+                line: 3,
+                message: "unexpected character 'o'".into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_writing_round_trips() {
+        let pattern = Pattern::new(vec![(0, 0), (2, 0), (1, 2)]);
+
+        let written = write(&pattern);
+        let parsed = parse(&written).unwrap();
+
+        assert_eq!(written, "#Life 1.05\n#N\n#P -1 -1\n*.*\n.\n.*\n");
+        assert_eq!(parsed, pattern.centered());
+    }
+}
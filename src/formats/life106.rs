@@ -0,0 +1,86 @@
+use crate::pattern::Pattern;
+
+use super::{FormatError, in_range};
+
+pub const HEADER: &str = "#Life 1.06";
+
+pub fn parse(input: &str) -> Result<Pattern, FormatError> {
+    let mut cells = Vec::new();
+
+    for (n, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut coords = line.split_whitespace().map(str::parse::<i64>);
+        match (coords.next(), coords.next(), coords.next()) {
+            (Some(Ok(x)), Some(Ok(y)), None) if in_range(x) && in_range(y) => {
+                cells.push((x, y));
+            }
+            (Some(Ok(_)), Some(Ok(_)), None) => return Err(FormatError::out_of_range(n)),
+            _ => return Err(FormatError::parse(n, "expected 'x y'")),
+        }
+    }
+
+    Ok(Pattern::new(cells))
+}
+
+/// Writes one `x y` line per live cell, centred on the origin.
+pub fn write(pattern: &Pattern) -> String {
+    let mut out = format!("{HEADER}\n");
+    for &(x, y) in pattern.centered().cells() {
+        out += &format!("{x} {y}\n");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parsing_coordinates() {
+        let input = "#Life 1.06\n0 -1\n1 0\n-1 1\n0 1\n1 1\n";
+
+        let pattern = parse(input).unwrap();
+
+        assert_eq!(pattern.cells(), &[(0, -1), (1, 0), (-1, 1), (0, 1), (1, 1)]);
+    }
+
+    #[test]
+    fn test_parsing_reports_malformed_lines() {
+        let result = parse("#Life 1.06\n0 0\n1 x\n");
+
+        assert_eq!(
+            result,
+            Err(FormatError::Parse {
+                line: 3,
+                message: "expected 'x y'".into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_parsing_rejects_extreme_coordinates() {
+        let result = parse("#Life 1.06\n-9223372036854775808 0\n9223372036854775807 0\n");
+
+        assert_eq!(
+            result,
+            Err(FormatError::Parse {
+                line: 2,
+                message: "coordinates out of range".into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_writing_round_trips() {
+        let pattern = Pattern::new(vec![(4, 4), (5, 4), (6, 4)]);
+
+        let written = write(&pattern);
+
+        assert_eq!(written, "#Life 1.06\n-1 0\n0 0\n1 0\n");
+        assert_eq!(parse(&written).unwrap(), pattern.centered());
+    }
+}
@@ -0,0 +1,375 @@
+use std::collections::HashMap;
+
+use crate::pattern::{Bounds, Pattern};
+
+use super::FormatError;
+
+pub const HEADER: &str = "[M2]";
+
+const LEAF_LEVEL: u32 = 3;
+const LEAF_SIZE: usize = 1 << LEAF_LEVEL;
+const MAX_LEVEL: u32 = 62;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum Node {
+    /// An 8x8 block, one byte per row with bit `x` set for live cells.
+    Leaf([u8; LEAF_SIZE]),
+    /// Children as 1-based node numbers in nw, ne, sw, se order; 0 is empty.
+    Inner { level: u32, children: [usize; 4] },
+}
+
+impl Node {
+    fn level(&self) -> u32 {
+        match self {
+            Node::Leaf(_) => LEAF_LEVEL,
+            Node::Inner { level, .. } => *level,
+        }
+    }
+}
+
+/// Reads a two-state macrocell file. The quadtree's bounding box is checked
+/// against `max_size` before any cell is expanded, so oversized universes fail
+/// fast instead of exhausting memory.
+pub fn parse(input: &str, max_size: (usize, usize)) -> Result<Pattern, FormatError> {
+    let mut nodes: Vec<Node> = Vec::new();
+
+    for (n, line) in input.lines().enumerate() {
+        let line = line.trim();
+
+        if line.starts_with(HEADER) || line.is_empty() {
+            continue;
+        }
+
+        if let Some(rule) = line.strip_prefix("#R") {
+            let rule = rule.trim();
+            if !rule.eq_ignore_ascii_case("B3/S23") {
+                return Err(FormatError::UnsupportedRule(rule.to_string()));
+            }
+            continue;
+        }
+
+        if line.starts_with('#') {
+            continue;
+        }
+
+        let node = if line.starts_with(['.', '*', '$']) {
+            parse_leaf(line).map_err(|message| FormatError::parse(n, message))?
+        } else {
+            parse_inner(line, nodes.len()).map_err(|message| FormatError::parse(n, message))?
+        };
+
+        if let Node::Inner { level, children } = &node {
+            for &child in children.iter().filter(|&&c| c != 0) {
+                if nodes[child - 1].level() + 1 != *level {
+                    return Err(FormatError::parse(n, "child node has the wrong level"));
+                }
+            }
+        }
+
+        nodes.push(node);
+    }
+
+    let Some(root) = nodes.len().checked_sub(1) else {
+        return Ok(Pattern::default());
+    };
+
+    let bounds = node_bounds(&nodes);
+    let Some(b) = bounds[root] else {
+        return Ok(Pattern::default());
+    };
+    b.check_fits(max_size.0, max_size.1)?;
+
+    let mut cells = Vec::new();
+    expand(&nodes, root + 1, (0, 0), &bounds, &mut cells);
+    Ok(Pattern::new(cells))
+}
+
+fn parse_leaf(line: &str) -> Result<Node, String> {
+    let mut rows = [0u8; LEAF_SIZE];
+    let (mut x, mut y) = (0, 0);
+
+    for c in line.chars() {
+        match c {
+            '.' => x += 1,
+            '*' => {
+                if x >= LEAF_SIZE || y >= LEAF_SIZE {
+                    return Err("leaf cell outside of 8x8 block".into());
+                }
+                rows[y] |= 1 << x;
+                x += 1;
+            }
+            '$' => {
+                x = 0;
+                y += 1;
+            }
+            _ => return Err(format!("unexpected character '{c}'")),
+        }
+    }
+
+    Ok(Node::Leaf(rows))
+}
+
+fn parse_inner(line: &str, defined: usize) -> Result<Node, String> {
+    let fields = line
+        .split_whitespace()
+        .map(str::parse::<usize>)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| "expected 'level nw ne sw se'".to_string())?;
+
+    let [level, nw, ne, sw, se] = fields[..] else {
+        return Err("expected 'level nw ne sw se'".into());
+    };
+
+    let level = level as u32;
+    if level <= LEAF_LEVEL || level > MAX_LEVEL {
+        return Err(format!("unsupported node level {level}"));
+    }
+
+    let children = [nw, ne, sw, se];
+    if children.iter().any(|&c| c > defined) {
+        return Err("reference to an undefined node".into());
+    }
+
+    Ok(Node::Inner { level, children })
+}
+
+/// Bounding box of each node's live cells, relative to the node's top-left.
+fn node_bounds(nodes: &[Node]) -> Vec<Option<Bounds>> {
+    let mut bounds: Vec<Option<Bounds>> = Vec::with_capacity(nodes.len());
+
+    for node in nodes {
+        let b = match node {
+            Node::Leaf(rows) => leaf_cells(rows)
+                .map(|(x, y)| Bounds {
+                    min_x: x,
+                    min_y: y,
+                    max_x: x,
+                    max_y: y,
+                })
+                .reduce(union),
+            Node::Inner { level, children } => {
+                let half = 1i64 << (level - 1);
+                children
+                    .iter()
+                    .zip(quadrant_offsets(half))
+                    .filter(|&(&c, _)| c != 0)
+                    .filter_map(|(&c, (dx, dy))| {
+                        bounds[c - 1].map(|b| Bounds {
+                            min_x: b.min_x + dx,
+                            min_y: b.min_y + dy,
+                            max_x: b.max_x + dx,
+                            max_y: b.max_y + dy,
+                        })
+                    })
+                    .reduce(union)
+            }
+        };
+        bounds.push(b);
+    }
+
+    bounds
+}
+
+fn union(a: Bounds, b: Bounds) -> Bounds {
+    Bounds {
+        min_x: a.min_x.min(b.min_x),
+        min_y: a.min_y.min(b.min_y),
+        max_x: a.max_x.max(b.max_x),
+        max_y: a.max_y.max(b.max_y),
+    }
+}
+
+fn quadrant_offsets(half: i64) -> [(i64, i64); 4] {
+    [(0, 0), (half, 0), (0, half), (half, half)]
+}
+
+fn leaf_cells(rows: &[u8; LEAF_SIZE]) -> impl Iterator<Item = (i64, i64)> + '_ {
+    rows.iter().enumerate().flat_map(|(y, &row)| {
+        (0..LEAF_SIZE)
+            .filter(move |&x| row & (1 << x) != 0)
+            .map(move |x| (x as i64, y as i64))
+    })
+}
+
+fn expand(
+    nodes: &[Node],
+    index: usize,
+    origin: (i64, i64),
+    bounds: &[Option<Bounds>],
+    cells: &mut Vec<(i64, i64)>,
+) {
+    if index == 0 || bounds[index - 1].is_none() {
+        return;
+    }
+
+    match &nodes[index - 1] {
+        Node::Leaf(rows) => {
+            cells.extend(leaf_cells(rows).map(|(x, y)| (origin.0 + x, origin.1 + y)));
+        }
+        Node::Inner { level, children } => {
+            let half = 1i64 << (level - 1);
+            for (&child, (dx, dy)) in children.iter().zip(quadrant_offsets(half)) {
+                expand(nodes, child, (origin.0 + dx, origin.1 + dy), bounds, cells);
+            }
+        }
+    }
+}
+
+/// Writes the pattern as a quadtree, sharing identical subtrees.
+pub fn write(pattern: &Pattern) -> String {
+    let mut out = format!("{HEADER} (petri-rs)\n#R B3/S23\n");
+    let pattern = pattern.centered();
+    let Some(b) = pattern.bounds() else {
+        return out;
+    };
+
+    let side = b.width().max(b.height()).next_power_of_two().max(LEAF_SIZE);
+    let mut alive = vec![false; side * side];
+    for &(x, y) in pattern.cells() {
+        alive[(y - b.min_y) as usize * side + (x - b.min_x) as usize] = true;
+    }
+
+    let mut writer = TreeWriter {
+        alive: &alive,
+        side,
+        ids: HashMap::new(),
+        lines: Vec::new(),
+    };
+    writer.node((0, 0), side.trailing_zeros());
+
+    for line in writer.lines {
+        out += &line;
+        out.push('\n');
+    }
+    out
+}
+
+struct TreeWriter<'a> {
+    alive: &'a [bool],
+    side: usize,
+    ids: HashMap<Node, usize>,
+    lines: Vec<String>,
+}
+
+impl TreeWriter<'_> {
+    /// Returns the 1-based number of the node covering the square at `origin`.
+    fn node(&mut self, origin: (usize, usize), level: u32) -> usize {
+        let node = if level == LEAF_LEVEL {
+            let mut rows = [0u8; LEAF_SIZE];
+            for (y, row) in rows.iter_mut().enumerate() {
+                for x in 0..LEAF_SIZE {
+                    if self.alive[(origin.1 + y) * self.side + origin.0 + x] {
+                        *row |= 1 << x;
+                    }
+                }
+            }
+            if rows == [0; LEAF_SIZE] {
+                return 0;
+            }
+            Node::Leaf(rows)
+        } else {
+            let half = 1usize << (level - 1);
+            let children = [(0, 0), (half, 0), (0, half), (half, half)]
+                .map(|(dx, dy)| self.node((origin.0 + dx, origin.1 + dy), level - 1));
+            if children == [0; 4] {
+                return 0;
+            }
+            Node::Inner { level, children }
+        };
+
+        if let Some(&id) = self.ids.get(&node) {
+            return id;
+        }
+
+        self.lines.push(node_line(&node));
+        self.ids.insert(node, self.lines.len());
+        self.lines.len()
+    }
+}
+
+fn node_line(node: &Node) -> String {
+    match node {
+        Node::Leaf(rows) => {
+            let last = rows.iter().rposition(|&r| r != 0).unwrap_or(0);
+            rows[..=last]
+                .iter()
+                .map(|&row| {
+                    let width = (u8::BITS - row.leading_zeros()) as usize;
+                    let cells: String = (0..width)
+                        .map(|x| if row & (1 << x) != 0 { '*' } else { '.' })
+                        .collect();
+                    cells + "$"
+                })
+                .collect()
+        }
+        Node::Inner { level, children } => {
+            let [nw, ne, sw, se] = children;
+            format!("{level} {nw} {ne} {sw} {se}")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pattern::PatternError;
+
+    const GLIDER: &str = "[M2] (golly 4.2)\n#R B3/S23\n$$$$$$..*$...*$\n.....***$\n4 1 0 2 0\n";
+
+    #[test]
+    fn test_parsing_glider() {
+        let pattern = parse(GLIDER, (100, 100)).unwrap();
+
+        assert_eq!(pattern.cells(), &[(2, 6), (3, 7), (5, 8), (6, 8), (7, 8)]);
+    }
+
+    #[test]
+    fn test_parsing_rejects_pattern_larger_than_limit_before_expanding() {
+        let input = "[M2]\n*$\n4 1 0 0 1\n5 2 0 0 2\n";
+
+        let result = parse(input, (16, 16));
+
+        assert_eq!(
+            result,
+            Err(FormatError::Pattern(PatternError::TooLarge {
+                width: 25,
+                height: 25,
+                grid_width: 16,
+                grid_height: 16,
+            }))
+        );
+    }
+
+    #[test]
+    fn test_parsing_rejects_undefined_nodes() {
+        let result = parse("[M2]\n*$\n4 1 2 0 0\n", (100, 100));
+
+        assert_eq!(
+            result,
+            Err(FormatError::Parse {
+                line: 3,
+                message: "reference to an undefined node".into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_writing_round_trips_and_shares_nodes() {
+        let blocks = Pattern::new(vec![
+            (0, 0),
+            (1, 0),
+            (0, 1),
+            (1, 1),
+            (8, 0),
+            (9, 0),
+            (8, 1),
+            (9, 1),
+        ]);
+
+        let written = write(&blocks);
+        let parsed = parse(&written, (100, 100)).unwrap();
+
+        assert_eq!(written.lines().filter(|l| l.starts_with('*')).count(), 1);
+        assert_eq!(parsed.centered(), blocks.centered());
+    }
+}
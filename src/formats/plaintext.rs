@@ -0,0 +1,107 @@
+use crate::pattern::Pattern;
+
+use super::FormatError;
+
+/// True when every line is a `!` comment or made of `.`, `O` and `*` only.
+pub fn looks_like(input: &str) -> bool {
+    let mut has_cells = false;
+    for line in input.lines().map(str::trim_end) {
+        if line.starts_with('!') {
+            continue;
+        }
+        if !line.chars().all(|c| matches!(c, '.' | 'O' | '*')) {
+            return false;
+        }
+        has_cells |= !line.is_empty();
+    }
+    has_cells
+}
+
+pub fn parse(input: &str) -> Result<Pattern, FormatError> {
+    let mut cells = Vec::new();
+    let mut row = 0;
+
+    for (n, line) in input.lines().enumerate() {
+        let line = line.trim_end();
+        if line.starts_with('!') {
+            continue;
+        }
+
+        for (x, c) in line.chars().enumerate() {
+            match c {
+                'O' | '*' => cells.push((x as i64, row)),
+                '.' => {}
+                _ => return Err(FormatError::parse(n, format!("unexpected character '{c}'"))),
+            }
+        }
+        row += 1;
+    }
+
+    Ok(Pattern::new(cells))
+}
+
+/// Writes the pattern's bounding box, one row of `.` and `O` per line.
+pub fn write(pattern: &Pattern) -> String {
+    let pattern = pattern.normalized();
+    let mut out = "!Name: petri-rs\n".to_string();
+    let Some(b) = pattern.bounds() else {
+        return out;
+    };
+
+    let mut cells = pattern.cells().iter().peekable();
+    for y in 0..=b.max_y {
+        let mut line = String::new();
+        while let Some(&(x, _)) = cells.next_if(|&&(_, cy)| cy == y) {
+            line += &".".repeat(x as usize - line.len());
+            line.push('O');
+        }
+        out += &line;
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parsing_glider() {
+        let input = "!Name: Glider\n!\n.O\n..O\nOOO\n";
+
+        let pattern = parse(input).unwrap();
+
+        assert_eq!(pattern.cells(), &[(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]);
+    }
+
+    #[test]
+    fn test_parsing_reports_unexpected_characters() {
+        let result = parse("!Name: x\nO.x\n");
+
+        assert_eq!(
+            result,
+            Err(FormatError::Parse {
+                line: 2,
+                message: "unexpected character 'x'".into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_writing_round_trips() {
+        let pattern = Pattern::new(vec![(3, 3), (5, 3), (4, 5)]);
+
+        let written = write(&pattern);
+
+        assert_eq!(written, "!Name: petri-rs\nO.O\n\n.O\n");
+        assert_eq!(parse(&written).unwrap(), pattern.normalized());
+    }
+
+    #[test]
+    fn test_recognising_plaintext() {
+        assert!(looks_like("!Name: Blinker\nOOO\n"));
+        assert!(looks_like(".O.\n"));
+        assert!(!looks_like("#Life 1.06\n0 0\n"));
+        assert!(!looks_like("!only a comment\n"));
+    }
+}
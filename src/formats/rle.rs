@@ -0,0 +1,266 @@
+use crate::pattern::{Bounds, Pattern};
+
+use super::FormatError;
+
+const LIFE_RULES: [&str; 2] = ["B3/S23", "23/3"];
+const MAX_LINE_LENGTH: usize = 70;
+
+/// True when the text has an RLE `x = .., y = ..` header line.
+pub fn has_header(input: &str) -> bool {
+    input
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('#'))
+        .is_some_and(|line| line.starts_with('x') && line.contains('='))
+}
+
+/// Reads an RLE pattern. Each run of live cells is checked against
+/// `max_size` before it is expanded, so huge run counts fail fast instead of
+/// exhausting memory.
+pub fn parse(input: &str, max_size: (usize, usize)) -> Result<Pattern, FormatError> {
+    let mut cells = Vec::new();
+    let (mut x, mut y) = (0i64, 0i64);
+    let mut seen_header = false;
+
+    'lines: for (n, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if !seen_header && line.starts_with('x') {
+            seen_header = true;
+            check_rule(line)?;
+            continue;
+        }
+
+        let mut run = String::new();
+        for c in line.chars() {
+            if c.is_ascii_digit() {
+                run.push(c);
+                continue;
+            }
+
+            let count = if run.is_empty() {
+                1
+            } else {
+                run.parse::<i64>()
+                    .map_err(|_| FormatError::parse(n, format!("run count '{run}' too large")))?
+            };
+            run.clear();
+
+            let too_far = || FormatError::parse(n, format!("run of {count} goes too far"));
+            match c {
+                'b' | '.' => x = x.checked_add(count).ok_or_else(too_far)?,
+                'o' | 'A' => {
+                    let end = x.checked_add(count).ok_or_else(too_far)?;
+                    if count > 0 {
+                        let run = Bounds {
+                            min_x: 0,
+                            min_y: 0,
+                            max_x: end - 1,
+                            max_y: y,
+                        };
+                        run.check_fits(max_size.0, max_size.1)?;
+                    }
+                    cells.extend((x..end).map(|cx| (cx, y)));
+                    x = end;
+                }
+                '$' => {
+                    y = y.checked_add(count).ok_or_else(too_far)?;
+                    x = 0;
+                }
+                '!' => break 'lines,
+                c if c.is_whitespace() => {}
+                _ => return Err(FormatError::parse(n, format!("unexpected character '{c}'"))),
+            }
+        }
+
+        if !run.is_empty() {
+            return Err(FormatError::parse(n, "run count at end of line"));
+        }
+    }
+
+    Ok(Pattern::new(cells))
+}
+
+fn check_rule(header: &str) -> Result<(), FormatError> {
+    let rule = header.split(',').find_map(|field| {
+        let (key, value) = field.split_once('=')?;
+        (key.trim() == "rule").then(|| value.trim())
+    });
+
+    match rule {
+        Some(rule) if !LIFE_RULES.iter().any(|r| r.eq_ignore_ascii_case(rule)) => {
+            Err(FormatError::UnsupportedRule(rule.to_string()))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Writes the pattern's bounding box as run-length encoded rows.
+pub fn write(pattern: &Pattern) -> String {
+    let pattern = pattern.normalized();
+    let Some(b) = pattern.bounds() else {
+        return "x = 0, y = 0, rule = B3/S23\n!\n".to_string();
+    };
+
+    let mut tokens = Vec::new();
+    let mut cells = pattern.cells().iter().peekable();
+    let mut pending_rows = 0;
+    for y in 0..=b.max_y {
+        let mut row = Vec::new();
+        while let Some(&(x, _)) = cells.next_if(|&&(_, cy)| cy == y) {
+            row.push(x);
+        }
+        if row.is_empty() {
+            pending_rows += 1;
+            continue;
+        }
+        if y > 0 {
+            tokens.push(run(pending_rows + 1, '$'));
+        }
+        pending_rows = 0;
+
+        let mut x = 0;
+        let mut i = 0;
+        while i < row.len() {
+            let start = row[i];
+            let mut end = start;
+            while i + 1 < row.len() && row[i + 1] == end + 1 {
+                i += 1;
+                end += 1;
+            }
+            if start > x {
+                tokens.push(run(start - x, 'b'));
+            }
+            tokens.push(run(end - start + 1, 'o'));
+            x = end + 1;
+            i += 1;
+        }
+    }
+    tokens.push("!".to_string());
+
+    let mut out = format!("x = {}, y = {}, rule = B3/S23\n", b.width(), b.height());
+    let mut line_length = 0;
+    for token in tokens {
+        if line_length + token.len() > MAX_LINE_LENGTH {
+            out.push('\n');
+            line_length = 0;
+        }
+        line_length += token.len();
+        out += &token;
+    }
+    out.push('\n');
+    out
+}
+
+fn run(count: i64, tag: char) -> String {
+    if count == 1 {
+        tag.to_string()
+    } else {
+        format!("{count}{tag}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pattern::PatternError;
+
+    fn glider() -> Pattern {
+        Pattern::new(vec![(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)])
+    }
+
+    #[test]
+    fn test_parsing_glider() {
+        let input = "#N Glider\n#C comment\nx = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n";
+
+        let pattern = parse(input, (100, 100)).unwrap();
+
+        assert_eq!(pattern, glider());
+    }
+
+    #[test]
+    fn test_parsing_runs_across_lines_and_blank_rows() {
+        let input = "x = 4, y = 3\n2o\n2$b\n3o!";
+
+        let pattern = parse(input, (100, 100)).unwrap();
+
+        assert_eq!(pattern.cells(), &[(0, 0), (1, 0), (1, 2), (2, 2), (3, 2)]);
+    }
+
+    #[test]
+    fn test_parsing_rejects_other_rules() {
+        let result = parse("x = 1, y = 1, rule = B36/S23\no!", (100, 100));
+
+        assert_eq!(result, Err(FormatError::UnsupportedRule("B36/S23".into())));
+    }
+
+    #[test]
+    fn test_parsing_rejects_runs_past_limit() {
+        let huge = parse("x = 1, y = 1\n9999999999o!", (500, 300));
+        let low = parse("x = 1, y = 1\n400$o!", (500, 300));
+        let overflow = parse("x = 1, y = 1\n9223372036854775807bo!", (500, 300));
+
+        assert_eq!(
+            huge,
+            Err(FormatError::Pattern(PatternError::TooLarge {
+                width: 9999999999,
+                height: 1,
+                grid_width: 500,
+                grid_height: 300,
+            }))
+        );
+        assert!(matches!(
+            low,
+            Err(FormatError::Pattern(PatternError::TooLarge {
+                height: 401,
+                ..
+            }))
+        ));
+        assert_eq!(
+            overflow,
+            Err(FormatError::Parse {
+                line: 2,
+                message: "run of 1 goes too far".into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_parsing_reports_unexpected_characters() {
+        let result = parse("x = 1, y = 1\noz!", (100, 100));
+
+        assert_eq!(
+            result,
+            Err(FormatError::Parse {
+                line: 2,
+                message: "unexpected character 'z'".into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_writing_round_trips() {
+        let pattern = glider().translated(5, 7);
+
+        let written = write(&pattern);
+
+        assert_eq!(written, "x = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n");
+        assert_eq!(parse(&written, (100, 100)).unwrap(), pattern.normalized());
+    }
+
+    #[test]
+    fn test_writing_compresses_blank_rows_and_wraps() {
+        let sparse = Pattern::new(vec![(0, 0), (0, 3)]);
+        let long = Pattern::new((0..100).map(|x| (x * 2, 0)).collect());
+
+        let sparse_written = write(&sparse);
+        let long_written = write(&long);
+
+        assert_eq!(sparse_written, "x = 1, y = 4, rule = B3/S23\no3$o!\n");
+        assert!(long_written.lines().all(|l| l.len() <= MAX_LINE_LENGTH));
+        assert_eq!(parse(&long_written, (200, 1)).unwrap(), long);
+    }
+}
@@ -1,7 +1,12 @@
 use std::fmt::Display;
 
-use crate::{grid::Grid, grid_evolver::GridEvolver};
+use crate::{
+    grid::{Grid, Region},
+    grid_evolver::GridEvolver,
+    period::PeriodSearch,
+};
 
+#[derive(Clone)]
 pub struct Game {
     generation: u32,
     pub grid: Grid,
@@ -15,6 +20,16 @@ impl Game {
         }
     }
 
+    /// Resumes counting from an earlier generation, as when restoring a session.
+    pub fn with_generation(mut self, generation: u32) -> Self {
+        self.generation = generation;
+        self
+    }
+
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+
     pub fn tick(&mut self) -> bool {
         let (new_cells, changed) = GridEvolver::next_generation(&self.grid)
             .expect("tick: computing next generation failed");
@@ -25,6 +40,22 @@ impl Game {
         self.generation += 1;
         changed
     }
+
+    /// The heading line of the `Display` output, naming the generation.
+    pub fn caption(&self) -> String {
+        self.to_string()
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .to_owned()
+    }
+
+    /// Starts looking for the grid, or just the contents of `region`, to
+    /// come back to an earlier state, anywhere on it, within
+    /// `max_generations`. The search runs on copies, a batch at a time.
+    pub fn period_search(&self, region: Option<Region>, max_generations: u32) -> PeriodSearch {
+        PeriodSearch::new(self, region, max_generations)
+    }
 }
 
 impl Display for Game {
@@ -33,3 +64,18 @@ impl Display for Game {
         write!(f, "{}", self.grid)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_caption_matches_display_heading() {
+        let game = Game::new(Grid::new(3, 2)).with_generation(42);
+
+        let caption = game.caption();
+
+        assert_eq!(caption, "gen: 42");
+        assert!(game.to_string().starts_with(&format!("{caption}\n")));
+    }
+}
@@ -1,10 +1,23 @@
 use std::time::{Duration, Instant};
 
-use crate::{game::Game, ui::PointerGridEvent};
+use crate::{
+    draw::{self, DrawSymmetry, Tool},
+    game::Game,
+    grid::{Anchor, Grid, Region},
+    history::GenerationHistory,
+    pattern::{PasteMode, Pattern, Transform},
+    snapshot::Snapshot,
+    soup::SoupOptions,
+    stability::{AutoPause, Settled, StabilityWatcher},
+    ui::PointerGridEvent,
+    undo::UndoStack,
+};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
 enum Playback {
     Playing,
+    /// Stepping back through the recorded generations.
+    Reversing,
     #[default]
     Paused,
 }
@@ -53,32 +66,195 @@ impl<T: TimeSource> GameClock<T> {
     }
 }
 
+/// What a secondary or two-button click does on the grid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PointerAction {
+    Nothing,
+    Toggle,
+    /// Paints live cells with the brush; dragging continues the stroke.
+    Draw,
+    /// Paints dead cells with the brush; dragging continues the stroke.
+    Erase,
+    /// Makes the drawing tools paint the state of the clicked cell.
+    Eyedropper,
+}
+
+impl PointerAction {
+    pub const ALL: [PointerAction; 5] = [
+        PointerAction::Nothing,
+        PointerAction::Toggle,
+        PointerAction::Draw,
+        PointerAction::Erase,
+        PointerAction::Eyedropper,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            PointerAction::Nothing => "Nothing",
+            PointerAction::Toggle => "Toggle cell",
+            PointerAction::Draw => "Draw",
+            PointerAction::Erase => "Erase",
+            PointerAction::Eyedropper => "Pick state",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PointerBindings {
+    pub right: PointerAction,
+    pub both: PointerAction,
+}
+
+impl Default for PointerBindings {
+    fn default() -> Self {
+        Self {
+            right: PointerAction::Erase,
+            both: PointerAction::Eyedropper,
+        }
+    }
+}
+
+/// Where a fast run, evolving without drawing each generation, stops.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RunGoal {
+    Generation(u32),
+    /// The first generation that differs from the one before.
+    Change,
+    /// Nothing alive, or a generation identical to the one before.
+    Settled,
+}
+
+/// How a fast run ended, with the generation it stopped at.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RunOutcome {
+    Reached(u32),
+    Changed(u32),
+    Extinct(u32),
+    Stable(u32),
+}
+
+/// A named copy of the game to jump back to.
+#[derive(Clone, Debug)]
+pub struct Bookmark {
+    pub name: String,
+    snapshot: Snapshot,
+}
+
+impl Bookmark {
+    pub fn generation(&self) -> u32 {
+        self.snapshot.generation()
+    }
+}
+
 pub struct GameController<T: TimeSource> {
     pub game: Game,
     playback: Playback,
     clock: GameClock<T>,
+    /// Pattern waiting to be placed, centred on the cursor.
+    paste: Option<Pattern>,
+    /// Keeps the paste floating after each placement, for library stamps.
+    stamping: bool,
+    pub paste_mode: PasteMode,
+    history: UndoStack,
+    generations: GenerationHistory,
+    /// Set by any edit the generation history has not yet seen.
+    edited: bool,
+    run: Option<RunGoal>,
+    /// The grid as it was when it last started evolving from generation 0.
+    start: Option<Snapshot>,
+    bookmarks: Vec<Bookmark>,
+    stability: StabilityWatcher,
+    /// Settling noticed by the last tick, not yet taken.
+    settled: Option<Settled>,
+    /// Set while a drag stroke is in progress so it undoes as one edit.
+    in_stroke: bool,
+    stroke_recorded: bool,
+    selection: Option<Region>,
+    /// Corner the selection is being dragged out from.
+    select_anchor: Option<(usize, usize)>,
+    /// Cell grabbed and region lifted while the selection is being moved;
+    /// its cells float in `paste` until the drag ends.
+    moving: Option<((usize, usize), Region)>,
+    pub tool: Tool,
+    pub brush_size: usize,
+    pub fill_shapes: bool,
+    /// Cell where the current brush stroke or shape drag started, and the
+    /// last cell it reached.
+    drawing: Option<((usize, usize), (usize, usize))>,
+    /// State the drawing tools paint.
+    pub draw_alive: bool,
+    pub bindings: PointerBindings,
+    /// Last cell and painted state of a right-button stroke.
+    secondary_stroke: Option<((usize, usize), bool)>,
+    /// Copies every pointer edit is repeated as.
+    pub symmetry: DrawSymmetry,
+    /// Centre of the symmetry in half cells, so it can sit between cells.
+    pub symmetry_centre: (i64, i64),
+    /// What playback stops for once the universe settles.
+    pub auto_pause: AutoPause,
 }
 
 impl<T: TimeSource> GameController<T> {
     pub fn new(game: Game, time_source: T) -> Self {
+        let symmetry_centre = grid_centre(&game.grid);
         Self {
             game,
             playback: Playback::default(),
             clock: GameClock::new(Duration::from_millis(500), time_source),
+            paste: None,
+            stamping: false,
+            paste_mode: PasteMode::default(),
+            history: UndoStack::default(),
+            generations: GenerationHistory::default(),
+            edited: false,
+            run: None,
+            start: None,
+            bookmarks: Vec::new(),
+            stability: StabilityWatcher::default(),
+            settled: None,
+            in_stroke: false,
+            stroke_recorded: false,
+            selection: None,
+            select_anchor: None,
+            moving: None,
+            tool: Tool::default(),
+            brush_size: 1,
+            fill_shapes: false,
+            drawing: None,
+            draw_alive: true,
+            bindings: PointerBindings::default(),
+            secondary_stroke: None,
+            symmetry: DrawSymmetry::default(),
+            symmetry_centre,
+            auto_pause: AutoPause::default(),
         }
     }
 
     pub fn play(&mut self) {
+        self.run = None;
         self.playback = Playback::Playing;
         self.tick();
     }
 
+    /// Stops playback and any fast run.
     pub fn pause(&mut self) {
         self.playback = Playback::Paused;
+        self.run = None;
+    }
+
+    /// Plays backwards until the recorded generations run out.
+    pub fn play_reverse(&mut self) {
+        self.playback = Playback::Reversing;
+        self.advance();
     }
 
+    /// True while playing in either direction.
     pub fn is_playing(&self) -> bool {
-        self.playback == Playback::Playing
+        self.playback != Playback::Paused
+    }
+
+    pub fn is_reversing(&self) -> bool {
+        self.playback == Playback::Reversing
     }
 
     pub fn with_interval(mut self, interval: Duration) -> Self {
@@ -86,42 +262,722 @@ impl<T: TimeSource> GameController<T> {
         self
     }
 
+    pub fn interval(&self) -> Duration {
+        self.clock.interval
+    }
+
     pub fn should_tick(&self) -> bool {
         self.is_playing() && self.clock.should_tick()
     }
 
+    /// Takes one step in the direction of playback.
+    pub fn advance(&mut self) {
+        if self.is_reversing() {
+            if !self.step_back() {
+                self.pause();
+            }
+        } else {
+            self.tick();
+        }
+    }
+
     pub fn tick(&mut self) {
-        let _changed = self.game.tick();
+        self.remember_start();
+        self.stability
+            .resume(&self.game, self.edited, &self.auto_pause);
+        self.sync_generations();
+        self.record_edit();
+        let changed = self.game.tick();
+        self.generations.record(&self.game, false);
+        self.edited = false;
+        self.clock.mark_tick();
+
+        if let Some(settled) = self
+            .stability
+            .observe(&self.game, changed, &self.auto_pause)
+        {
+            self.pause();
+            self.settled = Some(settled);
+        }
+    }
+
+    /// How the universe settled, if a tick has noticed since last asked.
+    /// Playback is paused when it happens.
+    pub fn take_settled(&mut self) -> Option<Settled> {
+        self.settled.take()
+    }
+
+    /// Returns to the previous generation; false when it is not recorded.
+    pub fn step_back(&mut self) -> bool {
+        match self.game.generation().checked_sub(1) {
+            Some(generation) => self.seek(generation),
+            None => false,
+        }
+    }
+
+    /// Jumps to a recorded generation, replaying from the nearest keyframe.
+    /// Editing there and stepping forward again replaces what came after.
+    pub fn seek(&mut self, generation: u32) -> bool {
+        self.sync_generations();
+        let Some(game) = self.generations.restore(generation) else {
+            return false;
+        };
+        self.game = game;
         self.clock.mark_tick();
+        true
+    }
+
+    /// Jumps to a recorded generation, or runs forward to a later one.
+    /// Returns false for a generation no longer recorded.
+    pub fn go_to_generation(&mut self, generation: u32) -> bool {
+        if self.seek(generation) {
+            return true;
+        }
+        if generation <= self.game.generation() {
+            return false;
+        }
+        self.start_run(RunGoal::Generation(generation));
+        true
+    }
+
+    /// Starts evolving toward the goal in batches driven by
+    /// [`Self::continue_run`]; the whole run undoes as one step.
+    pub fn start_run(&mut self, goal: RunGoal) {
+        self.pause();
+        self.remember_start();
+        self.sync_generations();
+        self.record_edit();
+        self.edited = false;
+        self.run = Some(goal);
+    }
+
+    /// Evolves up to `max_steps` generations toward the goal, returning how
+    /// the run ended once it has.
+    pub fn continue_run(&mut self, max_steps: u32) -> Option<RunOutcome> {
+        let goal = self.run?;
+        let outcome = match goal {
+            RunGoal::Generation(target) if self.game.generation() >= target => {
+                Some(RunOutcome::Reached(self.game.generation()))
+            }
+            _ => (0..max_steps).find_map(|_| {
+                let changed = self.game.tick();
+                self.generations.record(&self.game, false);
+                self.run_outcome(goal, changed)
+            }),
+        };
+        if outcome.is_some() {
+            self.run = None;
+        }
+        outcome
+    }
+
+    pub fn cancel_run(&mut self) {
+        self.run = None;
+    }
+
+    pub fn run_goal(&self) -> Option<RunGoal> {
+        self.run
+    }
+
+    fn run_outcome(&self, goal: RunGoal, changed: bool) -> Option<RunOutcome> {
+        let generation = self.game.generation();
+        match goal {
+            RunGoal::Generation(target) if generation >= target => {
+                Some(RunOutcome::Reached(generation))
+            }
+            RunGoal::Change if changed => Some(RunOutcome::Changed(generation)),
+            RunGoal::Settled if self.game.grid.live_cells().next().is_none() => {
+                Some(RunOutcome::Extinct(generation))
+            }
+            RunGoal::Settled if !changed => Some(RunOutcome::Stable(generation)),
+            _ => None,
+        }
+    }
+
+    /// Goes back to the grid as it was when it started evolving.
+    pub fn reset(&mut self) -> bool {
+        let Some(start) = self.start.clone() else {
+            return false;
+        };
+        self.jump_to(&start);
+        true
+    }
+
+    pub fn can_reset(&self) -> bool {
+        self.start.is_some()
+    }
+
+    pub fn add_bookmark(&mut self, name: impl Into<String>) {
+        self.bookmarks.push(Bookmark {
+            name: name.into(),
+            snapshot: Snapshot::capture(&self.game),
+        });
+    }
+
+    pub fn bookmarks(&self) -> &[Bookmark] {
+        &self.bookmarks
+    }
+
+    pub fn jump_to_bookmark(&mut self, index: usize) {
+        if let Some(bookmark) = self.bookmarks.get(index) {
+            let snapshot = bookmark.snapshot.clone();
+            self.jump_to(&snapshot);
+        }
+    }
+
+    pub fn remove_bookmark(&mut self, index: usize) {
+        if index < self.bookmarks.len() {
+            self.bookmarks.remove(index);
+        }
+    }
+
+    fn remember_start(&mut self) {
+        if self.game.generation() == 0 {
+            self.start = Some(Snapshot::capture(&self.game));
+        }
+    }
+
+    /// Restores a saved game as an undoable edit. The history is kept when it
+    /// already holds that same generation, and forked otherwise.
+    fn jump_to(&mut self, snapshot: &Snapshot) {
+        self.pause();
+        self.sync_generations();
+        self.record_edit();
+        self.reset_selection();
+        let recorded = self
+            .generations
+            .restore(snapshot.generation())
+            .is_some_and(|game| Snapshot::capture(&game) == *snapshot);
+        self.game = snapshot.restore();
+        self.edited = !recorded;
+    }
+
+    pub fn can_step_back(&self) -> bool {
+        self.generations
+            .range()
+            .is_some_and(|range| *range.start() < self.game.generation())
+    }
+
+    /// Generations visited so far, for the timeline.
+    pub fn generations(&self) -> &GenerationHistory {
+        &self.generations
+    }
+
+    /// Brings the history up to date with the current game, forking it if
+    /// the game was edited since it was last recorded.
+    fn sync_generations(&mut self) {
+        self.generations.record(&self.game, self.edited);
+        self.edited = false;
+    }
+
+    /// Starts a fresh game on the given grid, pausing playback.
+    pub fn replace_grid(&mut self, grid: Grid) {
+        self.pause();
+        self.record_edit();
+        self.reset_selection();
+        self.generations.clear();
+        self.start = None;
+        self.game = Game::new(grid);
+    }
+
+    /// Kills every cell, keeping the generation counter.
+    pub fn clear(&mut self) {
+        self.pause();
+        self.record_edit();
+        self.game.grid = Grid::new(self.game.grid.width(), self.game.grid.height());
+    }
+
+    /// Changes the grid's size, keeping cells around the anchor.
+    pub fn resize_grid(&mut self, width: usize, height: usize, anchor: Anchor) {
+        let grid = self.game.grid.resized(width, height, anchor);
+        self.reshape(grid);
+    }
+
+    pub fn translate_grid(&mut self, dx: i64, dy: i64) {
+        let grid = self.game.grid.translated(dx, dy);
+        self.reshape(grid);
+    }
+
+    /// Shrinks the grid to the live cells plus a margin; returns false when
+    /// there is nothing alive to crop to.
+    pub fn crop_to_live(&mut self, margin: usize) -> bool {
+        let Some(grid) = self.game.grid.cropped_to_live(margin) else {
+            return false;
+        };
+        self.reshape(grid);
+        true
+    }
+
+    /// Swaps in a reworked grid, keeping the generation counter.
+    fn reshape(&mut self, grid: Grid) {
+        self.pause();
+        self.record_edit();
+        self.reset_selection();
+        self.generations.clear();
+        self.start = None;
+        self.game.grid = grid;
+    }
+
+    /// Fills the selection, or the whole grid when nothing is selected, with
+    /// a random soup.
+    pub fn randomize(&mut self, options: &SoupOptions) {
+        self.pause();
+        self.record_edit();
+        match self.selection {
+            Some(region) => {
+                if self.game.grid.randomize_region(region, options).is_err() {
+                    eprintln!("Error: selection outside of grid");
+                }
+            }
+            None => self.game.grid.randomize(options),
+        }
+    }
+
+    pub fn undo(&mut self) {
+        self.pause();
+        if let Some(game) = self.history.undo(&self.game) {
+            self.reset_selection();
+            self.game = game;
+            self.edited = true;
+        }
+    }
+
+    pub fn redo(&mut self) {
+        self.pause();
+        if let Some(game) = self.history.redo(&self.game) {
+            self.reset_selection();
+            self.game = game;
+            self.edited = true;
+        }
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.history.can_undo()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.history.can_redo()
+    }
+
+    /// Groups every edit until `end_stroke` into a single undo entry.
+    pub fn begin_stroke(&mut self) {
+        self.in_stroke = true;
+        self.stroke_recorded = false;
+    }
+
+    pub fn end_stroke(&mut self) {
+        self.in_stroke = false;
+        self.stroke_recorded = false;
+    }
+
+    fn record_edit(&mut self) {
+        self.edited = true;
+        if self.stroke_recorded {
+            return;
+        }
+        self.history.record(&self.game);
+        self.stroke_recorded = self.in_stroke;
+    }
+
+    pub fn selection(&self) -> Option<Region> {
+        self.selection
+    }
+
+    pub fn select_none(&mut self) {
+        self.selection = None;
+    }
+
+    /// Live cells of the selection, relative to its top-left corner.
+    pub fn selection_pattern(&self) -> Option<Pattern> {
+        let region = self.selection?;
+        let cropped = self
+            .game
+            .grid
+            .cropped(region)
+            .expect("selection_pattern: selection outside of grid");
+        Some(Pattern::from_grid(&cropped))
+    }
+
+    /// What an export covers: the selection cropped out, or else the whole grid.
+    pub fn export_grid(&self) -> Grid {
+        match self.selection {
+            Some(region) => self
+                .game
+                .grid
+                .cropped(region)
+                .expect("export_grid: selection outside of grid"),
+            None => self.game.grid.clone(),
+        }
+    }
+
+    pub fn cut_selection(&mut self) -> Option<Pattern> {
+        let pattern = self.selection_pattern()?;
+        self.clear_selection();
+        Some(pattern)
+    }
+
+    pub fn clear_selection(&mut self) {
+        self.edit_selection(|grid, region| grid.fill_region(region, false));
+    }
+
+    pub fn fill_selection(&mut self) {
+        self.edit_selection(|grid, region| grid.fill_region(region, true));
+    }
+
+    pub fn invert_selection(&mut self) {
+        self.edit_selection(Grid::invert_region);
+    }
+
+    /// Transforms the selected cells in place; the selection follows the cells
+    /// when the transform changes its shape.
+    pub fn transform_selection(&mut self, transform: Transform) {
+        let Some(region) = self.selection else {
+            return;
+        };
+        self.pause();
+        self.record_edit();
+        match self.game.grid.transform_region(region, transform) {
+            Ok(region) => self.selection = Some(region),
+            Err(_) => eprintln!("Error: selection outside of grid"),
+        }
+    }
+
+    /// Shrinks the universe to the selection, keeping the generation counter.
+    pub fn crop_to_selection(&mut self) {
+        let Some(region) = self.selection else {
+            return;
+        };
+        self.pause();
+        self.record_edit();
+        self.game.grid = self
+            .game
+            .grid
+            .cropped(region)
+            .expect("crop_to_selection: selection outside of grid");
+        self.selection = None;
+    }
+
+    fn edit_selection<E>(&mut self, edit: impl FnOnce(&mut Grid, Region) -> Result<(), E>) {
+        let Some(region) = self.selection else {
+            return;
+        };
+        self.pause();
+        self.record_edit();
+        if edit(&mut self.game.grid, region).is_err() {
+            eprintln!("Error: selection outside of grid");
+        }
+    }
+
+    fn reset_selection(&mut self) {
+        self.selection = None;
+        self.select_anchor = None;
+        if self.moving.take().is_some() {
+            self.paste = None;
+        }
+    }
+
+    /// Floats the pattern under the cursor until the next left click places it.
+    pub fn start_paste(&mut self, pattern: Pattern) {
+        self.paste = Some(pattern.centered());
+        self.stamping = false;
+    }
+
+    /// Like [`Self::start_paste`], but every click places another copy until
+    /// the paste is cancelled.
+    pub fn start_stamp(&mut self, pattern: Pattern) {
+        self.start_paste(pattern);
+        self.stamping = true;
+    }
+
+    pub fn is_stamping(&self) -> bool {
+        self.stamping && self.paste.is_some()
+    }
+
+    pub fn paste_preview(&self) -> Option<&Pattern> {
+        self.paste.as_ref()
+    }
+
+    pub fn transform_paste(&mut self, transform: Transform) {
+        if let Some(pattern) = &mut self.paste {
+            *pattern = pattern.transformed(transform).centered();
+        }
+    }
+
+    pub fn cancel_paste(&mut self) {
+        self.paste = None;
+        self.stamping = false;
     }
 
     pub fn handle_pointer_event(&mut self, event: PointerGridEvent) {
         match event {
             PointerGridEvent::Hovered { cell: _ } => {}
             PointerGridEvent::LeftClick { cell } => self.on_left_click(cell),
-            PointerGridEvent::RightClick { cell: _ } => {}
-            PointerGridEvent::BothClick { cell: _ } => {}
+            PointerGridEvent::RightClick { cell } => {
+                self.apply_pointer_action(self.bindings.right, cell, true)
+            }
+            PointerGridEvent::BothClick { cell } => {
+                self.apply_pointer_action(self.bindings.both, cell, false)
+            }
+            PointerGridEvent::SelectStart { cell } => {
+                self.select_anchor = Some(cell);
+                self.selection = Some(Region::from_corners(cell, cell));
+            }
+            PointerGridEvent::DragMove { cell } => self.on_drag(cell),
+            PointerGridEvent::DragEnd { cell } => {
+                self.on_drag(cell);
+                self.select_anchor = None;
+                self.finish_move(cell);
+                self.finish_drawing();
+                self.secondary_stroke = None;
+            }
         };
     }
 
-    fn on_left_click(&mut self, coord: (usize, usize)) {
-        if self.is_playing() {
+    /// Drawing leaves forward playback running, but stops a fast run or
+    /// reverse playback, either of which would replace the edit.
+    fn stop_for_drawing(&mut self) {
+        if self.run.is_some() || self.is_reversing() {
             self.pause();
         }
+    }
 
-        match self.game.grid.toggle_cell_at_coord(coord) {
-            Ok(_) => (),
-            Err(_) => {
-                eprintln!("Error: Could not toggle");
+    fn apply_pointer_action(&mut self, action: PointerAction, cell: (usize, usize), drags: bool) {
+        let alive = match action {
+            PointerAction::Nothing => return,
+            PointerAction::Eyedropper => {
+                self.draw_alive = self.game.grid.get_cell_at_coord(cell).unwrap_or(false);
+                return;
+            }
+            PointerAction::Toggle => {
+                self.stop_for_drawing();
+                self.record_edit();
+                self.toggle(cell);
+                return;
             }
+            PointerAction::Draw => true,
+            PointerAction::Erase => false,
         };
+
+        self.stop_for_drawing();
+        self.record_edit();
+        let (x, y) = signed(cell);
+        self.paint(&draw::brush(self.brush_size).translated(x, y), alive);
+        if drags {
+            self.secondary_stroke = Some((cell, alive));
+        }
+    }
+
+    fn on_drag(&mut self, cell: (usize, usize)) {
+        if let Some((last, alive)) = self.secondary_stroke {
+            let segment = draw::line(signed(last), signed(cell));
+            self.paint(
+                &draw::stroke(&segment, &draw::brush(self.brush_size)),
+                alive,
+            );
+            self.secondary_stroke = Some((cell, alive));
+        } else if let Some(anchor) = self.select_anchor {
+            self.selection = Some(Region::from_corners(anchor, cell));
+        } else if let Some((grab, region)) = self.moving {
+            self.selection = self.moved_region(grab, region, cell);
+        } else if let Some((start, last)) = self.drawing {
+            if self.tool == Tool::Brush {
+                let segment = draw::line(signed(last), signed(cell));
+                self.paint(
+                    &draw::stroke(&segment, &draw::brush(self.brush_size)),
+                    self.draw_alive,
+                );
+            }
+            self.drawing = Some((start, cell));
+        }
+    }
+
+    /// What to draw translucently around the pointer's cell: the floating
+    /// paste, the shape being dragged out, or the brush footprint.
+    pub fn preview(&self) -> Option<Pattern> {
+        if let Some(pattern) = &self.paste {
+            return Some(pattern.clone());
+        }
+
+        if let Some((start, last)) = self.drawing
+            && self.tool.is_shape()
+        {
+            let (x, y) = signed(last);
+            return Some(self.shape(start, last).translated(-x, -y));
+        }
+
+        (self.tool != Tool::Toggle).then(|| draw::brush(self.brush_size))
     }
+
+    fn shape(&self, from: (usize, usize), to: (usize, usize)) -> Pattern {
+        let (from, to) = (signed(from), signed(to));
+        let outline = match self.tool {
+            Tool::Rectangle => draw::rectangle(from, to, self.fill_shapes),
+            Tool::Ellipse => draw::ellipse(from, to, self.fill_shapes),
+            _ => draw::line(from, to),
+        };
+
+        if self.fill_shapes && self.tool != Tool::Line {
+            outline
+        } else {
+            draw::stroke(&outline, &draw::brush(self.brush_size))
+        }
+    }
+
+    fn finish_drawing(&mut self) {
+        if let Some((start, last)) = self.drawing.take()
+            && self.tool.is_shape()
+        {
+            self.record_edit();
+            self.paint(&self.shape(start, last), self.draw_alive);
+        }
+    }
+
+    /// Puts the symmetry centre back in the middle of the grid.
+    pub fn centre_symmetry(&mut self) {
+        self.symmetry_centre = grid_centre(&self.game.grid);
+    }
+
+    /// Toggles the cell and its symmetric copies.
+    fn toggle(&mut self, cell: (usize, usize)) {
+        for &cell in self.symmetric(&Pattern::new(vec![signed(cell)])).cells() {
+            if let Some(coord) = self.game.grid.checked_coord(cell) {
+                let _ = self.game.grid.toggle_cell_at_coord(coord);
+            }
+        }
+    }
+
+    fn symmetric(&self, pattern: &Pattern) -> Pattern {
+        draw::symmetric(pattern, self.symmetry, self.symmetry_centre)
+    }
+
+    fn paint(&mut self, pattern: &Pattern, alive: bool) {
+        for &cell in self.symmetric(pattern).cells() {
+            if let Some(coord) = self.game.grid.checked_coord(cell) {
+                let _ = self.game.grid.set_cell_at_coord(coord, alive);
+            }
+        }
+    }
+
+    /// Lifts the selected cells into the paste layer so they follow the pointer.
+    fn begin_move(&mut self, grab: (usize, usize), region: Region) {
+        let Some(pattern) = self.selection_pattern() else {
+            return;
+        };
+        let _ = self.game.grid.fill_region(region, false);
+
+        let (dx, dy) = (
+            region.x as i64 - grab.0 as i64,
+            region.y as i64 - grab.1 as i64,
+        );
+        self.paste = Some(pattern.translated(dx, dy));
+        self.stamping = false;
+        self.moving = Some((grab, region));
+    }
+
+    /// Drops the lifted cells over whatever lies under the moved selection.
+    fn finish_move(&mut self, cell: (usize, usize)) {
+        let Some((grab, region)) = self.moving.take() else {
+            return;
+        };
+
+        let target = self.moved_region(grab, region, cell);
+        if let Some(target) = target {
+            let _ = self.game.grid.fill_region(target, false);
+        }
+        if let Some(pattern) = self.paste.take() {
+            let origin = (cell.0 as i64, cell.1 as i64);
+            pattern.paste_onto(&mut self.game.grid, origin, PasteMode::Or);
+        }
+        self.selection = target;
+    }
+
+    /// Where `region` lands when the cell grabbed at `grab` is dragged to
+    /// `cell`, clipped to the grid.
+    fn moved_region(
+        &self,
+        grab: (usize, usize),
+        region: Region,
+        cell: (usize, usize),
+    ) -> Option<Region> {
+        let x = region.x as i64 + cell.0 as i64 - grab.0 as i64;
+        let y = region.y as i64 + cell.1 as i64 - grab.1 as i64;
+        let left = x.max(0);
+        let top = y.max(0);
+        let right = (x + region.width as i64).min(self.game.grid.width() as i64);
+        let bottom = (y + region.height as i64).min(self.game.grid.height() as i64);
+
+        (left < right && top < bottom).then(|| Region {
+            x: left as usize,
+            y: top as usize,
+            width: (right - left) as usize,
+            height: (bottom - top) as usize,
+        })
+    }
+
+    /// Lifting the selection or placing a paste pauses, so the cells land
+    /// where they were aimed; drawing is done as in [`Self::stop_for_drawing`].
+    fn on_left_click(&mut self, coord: (usize, usize)) {
+        self.stop_for_drawing();
+
+        // A click outside the selection only dismisses it.
+        if self.paste.is_none()
+            && let Some(region) = self.selection
+        {
+            if region.contains(coord) {
+                self.pause();
+                self.record_edit();
+                self.begin_move(coord, region);
+            } else {
+                self.selection = None;
+            }
+            return;
+        }
+
+        if let Some(pattern) = self.paste.take() {
+            self.pause();
+            self.record_edit();
+            let (x, y) = signed(coord);
+            let placed = self.symmetric(&pattern.translated(x, y));
+            placed.paste_onto(&mut self.game.grid, (0, 0), self.paste_mode);
+            if self.stamping {
+                self.paste = Some(pattern);
+            }
+            return;
+        }
+
+        match self.tool {
+            Tool::Toggle => {
+                self.record_edit();
+                self.toggle(coord);
+            }
+            Tool::Brush => {
+                self.record_edit();
+                let (x, y) = signed(coord);
+                self.paint(
+                    &draw::brush(self.brush_size).translated(x, y),
+                    self.draw_alive,
+                );
+                self.drawing = Some((coord, coord));
+            }
+            Tool::Line | Tool::Rectangle | Tool::Ellipse => {
+                self.drawing = Some((coord, coord));
+            }
+        }
+    }
+}
+
+fn signed((x, y): (usize, usize)) -> (i64, i64) {
+    (x as i64, y as i64)
+}
+
+/// Centre of the grid in half cells.
+fn grid_centre(grid: &Grid) -> (i64, i64) {
+    (grid.width() as i64 - 1, grid.height() as i64 - 1)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::grid::Grid;
 
     #[test]
     fn playback_defaults_to_paused() {
@@ -197,6 +1053,204 @@ mod tests {
         controller.tick();
     }
 
+    #[test]
+    fn test_step_back_and_fork_history() {
+        let mut controller = controller_with_cells(&[(1, 2), (2, 2), (3, 2)]);
+        controller.tick();
+        controller.tick();
+
+        assert!(controller.step_back());
+        let vertical = controller.game.grid.live_cells().collect::<Vec<_>>();
+        let generation = controller.game.generation();
+        controller.handle_pointer_event(PointerGridEvent::LeftClick { cell: (0, 0) });
+        controller.tick();
+        assert!(controller.step_back());
+        assert!(controller.step_back());
+        let exhausted = !controller.step_back();
+
+        assert_eq!(vertical, vec![(2, 1), (2, 2), (2, 3)]);
+        assert_eq!(generation, 1);
+        assert!(exhausted);
+        assert_eq!(controller.game.generation(), 0);
+    }
+
+    #[test]
+    fn test_seek_scrubs_both_ways_until_edited() {
+        let mut controller = controller_with_cells(&[(1, 2), (2, 2), (3, 2)]);
+        for _ in 0..3 {
+            controller.tick();
+        }
+        let latest = controller.game.grid.live_cells().collect::<Vec<_>>();
+
+        controller.seek(1);
+        let forward = controller.seek(3);
+        let after_forward = controller.game.grid.live_cells().collect::<Vec<_>>();
+        controller.seek(1);
+        controller.handle_pointer_event(PointerGridEvent::LeftClick { cell: (0, 0) });
+
+        assert!(forward);
+        assert_eq!(after_forward, latest);
+        assert!(!controller.seek(3));
+        assert_eq!(controller.game.generation(), 1);
+    }
+
+    #[test]
+    fn test_run_to_generation_in_batches_undoes_as_one() {
+        let mut controller = controller_with_cells(&[(1, 2), (2, 2), (3, 2)]);
+
+        assert!(controller.go_to_generation(5));
+        let partial = controller.continue_run(3);
+        let finished = controller.continue_run(3);
+        controller.undo();
+
+        assert_eq!(partial, None);
+        assert_eq!(finished, Some(RunOutcome::Reached(5)));
+        assert_eq!(controller.run_goal(), None);
+        assert_eq!(controller.game.generation(), 0);
+    }
+
+    #[test]
+    fn test_run_until_settled_or_changed() {
+        let mut pond = controller_with_cells(&[(1, 1), (2, 1), (1, 2)]);
+        let mut lonely = controller_with_cells(&[(1, 1), (4, 4)]);
+        let mut blinker = controller_with_cells(&[(1, 2), (2, 2), (3, 2)]);
+
+        pond.start_run(RunGoal::Settled);
+        lonely.start_run(RunGoal::Settled);
+        blinker.start_run(RunGoal::Change);
+
+        assert_eq!(pond.continue_run(10), Some(RunOutcome::Stable(2)));
+        assert_eq!(lonely.continue_run(10), Some(RunOutcome::Extinct(1)));
+        assert_eq!(blinker.continue_run(10), Some(RunOutcome::Changed(1)));
+    }
+
+    #[test]
+    fn test_clicking_cancels_run() {
+        let mut controller = controller_with_cells(&[(1, 2), (2, 2), (3, 2)]);
+        controller.start_run(RunGoal::Settled);
+
+        controller.handle_pointer_event(PointerGridEvent::LeftClick { cell: (0, 0) });
+
+        assert_eq!(controller.continue_run(10), None);
+        assert_eq!(controller.game.generation(), 0);
+    }
+
+    #[test]
+    fn test_playback_pauses_once_settled() {
+        let mut controller = controller_with_cells(&[(1, 2), (2, 2), (3, 2)]);
+
+        controller.play();
+        controller.tick();
+        let settled = controller.take_settled();
+        controller.play();
+        controller.tick();
+
+        assert_eq!(
+            settled,
+            Some(Settled::Periodic {
+                period: 2,
+                since: 0
+            })
+        );
+        assert!(controller.is_playing());
+        assert_eq!(controller.take_settled(), None);
+    }
+
+    #[test]
+    fn test_auto_pause_can_be_turned_off() {
+        let mut controller = controller_with_cells(&[(1, 1), (2, 1), (1, 2), (2, 2)]);
+        controller.auto_pause.stable = false;
+
+        controller.play();
+        controller.tick();
+
+        assert!(controller.is_playing());
+        assert_eq!(controller.take_settled(), None);
+    }
+
+    #[test]
+    fn test_reset_restores_grid_from_first_play() {
+        let mut controller = controller_with_cells(&[(1, 2), (2, 2), (3, 2)]);
+        let drawn = controller.game.grid.live_cells().collect::<Vec<_>>();
+
+        let before_play = controller.reset();
+        controller.play();
+        controller.tick();
+        controller.reset();
+
+        assert!(!before_play);
+        assert!(!controller.is_playing());
+        assert_eq!(controller.game.generation(), 0);
+        assert_eq!(controller.game.grid.live_cells().collect::<Vec<_>>(), drawn);
+        assert!(controller.seek(2));
+    }
+
+    #[test]
+    fn test_bookmarks_jump_back_and_undo() {
+        let mut controller = controller_with_cells(&[(1, 2), (2, 2), (3, 2)]);
+        controller.tick();
+        controller.add_bookmark("vertical");
+        controller.tick();
+        controller.tick();
+
+        controller.jump_to_bookmark(0);
+        let jumped = controller.game.generation();
+        controller.undo();
+
+        assert_eq!(controller.bookmarks()[0].name, "vertical");
+        assert_eq!(controller.bookmarks()[0].generation(), 1);
+        assert_eq!(jumped, 1);
+        assert_eq!(controller.game.generation(), 3);
+        controller.remove_bookmark(0);
+        assert!(controller.bookmarks().is_empty());
+    }
+
+    #[test]
+    fn test_reverse_playback_pauses_at_oldest_generation() {
+        let mut controller = controller_with_cells(&[(1, 2), (2, 2), (3, 2)]);
+        controller.tick();
+
+        controller.play_reverse();
+        let reversing = controller.is_reversing();
+        controller.advance();
+
+        assert!(reversing);
+        assert!(!controller.is_playing());
+        assert_eq!(controller.game.generation(), 0);
+    }
+
+    #[test]
+    fn test_undo_forgets_generations_after_restored_one() {
+        let mut controller = controller_with_cells(&[(1, 2), (2, 2), (3, 2)]);
+        controller.tick();
+        controller.tick();
+
+        controller.undo();
+        controller.step_back();
+
+        assert_eq!(controller.game.generation(), 0);
+        assert!(!controller.can_step_back());
+    }
+
+    #[test]
+    fn test_replace_grid_resets_game_and_pauses() {
+        let mut controller = GameController::new(
+            Game::new(Grid::new(3, 3)),
+            MockClock {
+                now: Instant::now(),
+            },
+        );
+        controller.play();
+
+        let mut grid = Grid::new(5, 4);
+        let _ = grid.set_cell_at_coord((4, 3), true);
+        controller.replace_grid(grid);
+
+        assert!(!controller.is_playing());
+        assert_eq!(controller.game.grid.width(), 5);
+        assert!(controller.game.grid.get_cell_at_coord((4, 3)).unwrap());
+    }
+
     #[test]
     fn test_handle_pointer_event_hovered_does_nothing() {
         let mut controller = GameController::new(
@@ -214,7 +1268,7 @@ mod tests {
     }
 
     #[test]
-    fn test_handle_pointer_event_left_click_toggles_cell_and_pause_game() {
+    fn test_handle_pointer_event_left_click_toggles_cell_and_keeps_playing() {
         let mut controller = GameController::new(
             Game::new(Grid::new(3, 3)),
             MockClock {
@@ -230,38 +1284,470 @@ mod tests {
         controller.handle_pointer_event(PointerGridEvent::LeftClick { cell });
 
         assert!(controller.game.grid.get_cell_at_coord(cell).unwrap());
-        assert!(!controller.is_playing())
+        assert!(controller.is_playing())
     }
 
     #[test]
-    fn test_handle_pointer_event_right_click_does_nothing() {
+    fn test_handle_pointer_event_right_click_erases() {
+        let mut grid = Grid::new(5, 5);
+        for cell in [(1, 2), (2, 2), (3, 2)] {
+            grid.set_cell_at_coord(cell, true).unwrap();
+        }
         let mut controller = GameController::new(
-            Game::new(Grid::new(2, 2)),
+            Game::new(grid),
             MockClock {
                 now: Instant::now(),
             },
         );
-        let cell = (0, 0);
-        assert!(!controller.game.grid.get_cell_at_coord(cell).unwrap());
+        controller.play();
+        let cell = (2, 1);
+        assert!(controller.game.grid.get_cell_at_coord(cell).unwrap());
 
         controller.handle_pointer_event(PointerGridEvent::RightClick { cell });
 
-        assert!(!controller.game.grid.get_cell_at_coord(cell).unwrap());
+        assert_eq!(
+            controller.game.grid.live_cells().collect::<Vec<_>>(),
+            vec![(2, 2), (2, 3)]
+        );
+        assert!(controller.is_playing());
     }
 
     #[test]
-    fn test_handle_pointer_event_both_click_does_nothing() {
+    fn test_handle_pointer_event_both_click_picks_state() {
+        let mut grid = Grid::new(2, 2);
+        grid.set_cell_at_coord((0, 0), true).unwrap();
         let mut controller = GameController::new(
-            Game::new(Grid::new(2, 2)),
+            Game::new(grid),
             MockClock {
                 now: Instant::now(),
             },
         );
-        let cell = (0, 0);
-        assert!(!controller.game.grid.get_cell_at_coord(cell).unwrap());
 
-        controller.handle_pointer_event(PointerGridEvent::BothClick { cell });
+        controller.handle_pointer_event(PointerGridEvent::BothClick { cell: (1, 1) });
+        let picked_dead = controller.draw_alive;
+        controller.handle_pointer_event(PointerGridEvent::BothClick { cell: (0, 0) });
 
-        assert!(!controller.game.grid.get_cell_at_coord(cell).unwrap());
+        assert!(!picked_dead);
+        assert!(controller.draw_alive);
+        assert_eq!(
+            controller.game.grid.live_cells().collect::<Vec<_>>(),
+            vec![(0, 0)]
+        );
+    }
+
+    #[test]
+    fn test_left_click_places_paste_instead_of_toggling() {
+        let mut controller = GameController::new(
+            Game::new(Grid::new(5, 5)),
+            MockClock {
+                now: Instant::now(),
+            },
+        );
+        controller.start_paste(Pattern::new(vec![(10, 10), (11, 10), (12, 10)]));
+        controller.transform_paste(Transform::Rotate90);
+
+        controller.handle_pointer_event(PointerGridEvent::LeftClick { cell: (2, 2) });
+
+        assert_eq!(
+            controller.game.grid.live_cells().collect::<Vec<_>>(),
+            vec![(2, 1), (2, 2), (2, 3)]
+        );
+        assert!(controller.paste_preview().is_none());
+    }
+
+    #[test]
+    fn test_symmetric_toggle_and_paste() {
+        let mut controller = controller_with_cells(&[]);
+        controller.symmetry = DrawSymmetry::Horizontal;
+
+        controller.handle_pointer_event(PointerGridEvent::LeftClick { cell: (1, 1) });
+        controller.start_paste(Pattern::new(vec![(0, 0)]));
+        controller.handle_pointer_event(PointerGridEvent::LeftClick { cell: (0, 4) });
+
+        assert_eq!(
+            controller.game.grid.live_cells().collect::<Vec<_>>(),
+            vec![(1, 1), (4, 1), (0, 4), (5, 4)]
+        );
+    }
+
+    #[test]
+    fn test_symmetric_brush_about_chosen_centre() {
+        let mut controller = controller_with_cells(&[]);
+        controller.tool = Tool::Brush;
+        controller.symmetry = DrawSymmetry::Eightfold;
+        controller.symmetry_centre = (4, 4);
+
+        controller.handle_pointer_event(PointerGridEvent::LeftClick { cell: (2, 0) });
+        controller.centre_symmetry();
+
+        assert_eq!(
+            controller.game.grid.live_cells().collect::<Vec<_>>(),
+            vec![(2, 0), (0, 2), (4, 2), (2, 4)]
+        );
+        assert_eq!(controller.symmetry_centre, (5, 5));
+    }
+
+    #[test]
+    fn test_stamp_stays_until_cancelled() {
+        let mut controller = controller_with_cells(&[]);
+        controller.start_stamp(Pattern::new(vec![(0, 0)]));
+
+        controller.handle_pointer_event(PointerGridEvent::LeftClick { cell: (1, 1) });
+        controller.handle_pointer_event(PointerGridEvent::LeftClick { cell: (4, 2) });
+        let stamping = controller.is_stamping();
+        controller.cancel_paste();
+
+        assert!(stamping);
+        assert!(!controller.is_stamping());
+        assert_eq!(
+            controller.game.grid.live_cells().collect::<Vec<_>>(),
+            vec![(1, 1), (4, 2)]
+        );
+    }
+
+    #[test]
+    fn test_cancelled_paste_leaves_grid_untouched() {
+        let mut controller = GameController::new(
+            Game::new(Grid::new(3, 3)),
+            MockClock {
+                now: Instant::now(),
+            },
+        );
+        controller.start_paste(Pattern::new(vec![(0, 0)]));
+
+        controller.cancel_paste();
+        controller.handle_pointer_event(PointerGridEvent::LeftClick { cell: (0, 0) });
+
+        assert_eq!(
+            controller.game.grid.live_cells().collect::<Vec<_>>(),
+            vec![(0, 0)]
+        );
+    }
+
+    #[test]
+    fn test_undo_and_redo_cell_toggle() {
+        let mut controller = GameController::new(
+            Game::new(Grid::new(3, 3)),
+            MockClock {
+                now: Instant::now(),
+            },
+        );
+        controller.handle_pointer_event(PointerGridEvent::LeftClick { cell: (1, 1) });
+
+        controller.undo();
+        let after_undo = controller.game.grid.get_cell_at_coord((1, 1)).unwrap();
+        controller.redo();
+        let after_redo = controller.game.grid.get_cell_at_coord((1, 1)).unwrap();
+
+        assert!(!after_undo);
+        assert!(after_redo);
+    }
+
+    #[test]
+    fn test_undo_generation_step() {
+        let mut grid = Grid::new(5, 5);
+        for x in 1..4 {
+            let _ = grid.set_cell_at_coord((x, 2), true);
+        }
+        let mut controller = GameController::new(
+            Game::new(grid),
+            MockClock {
+                now: Instant::now(),
+            },
+        );
+        controller.tick();
+
+        controller.undo();
+
+        assert_eq!(controller.game.generation(), 0);
+        assert_eq!(
+            controller.game.grid.live_cells().collect::<Vec<_>>(),
+            vec![(1, 2), (2, 2), (3, 2)]
+        );
+    }
+
+    #[test]
+    fn test_stroke_undoes_as_one_edit() {
+        let mut controller = GameController::new(
+            Game::new(Grid::new(3, 3)),
+            MockClock {
+                now: Instant::now(),
+            },
+        );
+        controller.begin_stroke();
+        controller.handle_pointer_event(PointerGridEvent::LeftClick { cell: (0, 0) });
+        controller.handle_pointer_event(PointerGridEvent::LeftClick { cell: (1, 0) });
+        controller.end_stroke();
+
+        controller.undo();
+
+        assert_eq!(controller.game.grid.live_cells().count(), 0);
+        assert!(!controller.can_undo());
+    }
+
+    fn controller_with_cells(cells: &[(usize, usize)]) -> GameController<MockClock> {
+        let mut grid = Grid::new(6, 6);
+        for &cell in cells {
+            let _ = grid.set_cell_at_coord(cell, true);
+        }
+        GameController::new(
+            Game::new(grid),
+            MockClock {
+                now: Instant::now(),
+            },
+        )
+    }
+
+    #[test]
+    fn test_shift_drag_selects_region() {
+        let mut controller = controller_with_cells(&[]);
+
+        controller.handle_pointer_event(PointerGridEvent::SelectStart { cell: (3, 1) });
+        controller.handle_pointer_event(PointerGridEvent::DragMove { cell: (1, 2) });
+        controller.handle_pointer_event(PointerGridEvent::DragEnd { cell: (1, 4) });
+
+        assert_eq!(
+            controller.selection(),
+            Some(Region::from_corners((1, 1), (3, 4)))
+        );
+        assert_eq!(controller.game.grid.live_cells().count(), 0);
+    }
+
+    #[test]
+    fn test_dragging_selection_moves_cells() {
+        let mut controller = controller_with_cells(&[(1, 1), (2, 2), (4, 4)]);
+        controller.handle_pointer_event(PointerGridEvent::SelectStart { cell: (1, 1) });
+        controller.handle_pointer_event(PointerGridEvent::DragEnd { cell: (2, 2) });
+
+        controller.begin_stroke();
+        controller.handle_pointer_event(PointerGridEvent::LeftClick { cell: (1, 1) });
+        controller.handle_pointer_event(PointerGridEvent::DragMove { cell: (3, 1) });
+        controller.handle_pointer_event(PointerGridEvent::DragEnd { cell: (3, 2) });
+        controller.end_stroke();
+
+        assert_eq!(
+            controller.game.grid.live_cells().collect::<Vec<_>>(),
+            vec![(3, 2), (4, 3), (4, 4)]
+        );
+        assert_eq!(
+            controller.selection(),
+            Some(Region::from_corners((3, 2), (4, 3)))
+        );
+
+        controller.undo();
+
+        assert_eq!(
+            controller.game.grid.live_cells().collect::<Vec<_>>(),
+            vec![(1, 1), (2, 2), (4, 4)]
+        );
+    }
+
+    #[test]
+    fn test_click_outside_selection_only_deselects() {
+        let mut controller = controller_with_cells(&[]);
+        controller.handle_pointer_event(PointerGridEvent::SelectStart { cell: (0, 0) });
+        controller.handle_pointer_event(PointerGridEvent::DragEnd { cell: (1, 1) });
+
+        controller.handle_pointer_event(PointerGridEvent::LeftClick { cell: (4, 4) });
+        let deselected = controller.selection();
+        let edited_by_deselecting = controller.can_undo();
+        controller.handle_pointer_event(PointerGridEvent::LeftClick { cell: (4, 4) });
+
+        assert!(deselected.is_none());
+        assert!(!edited_by_deselecting);
+        assert_eq!(
+            controller.game.grid.live_cells().collect::<Vec<_>>(),
+            vec![(4, 4)]
+        );
+    }
+
+    #[test]
+    fn test_export_covers_selection_or_whole_grid() {
+        let mut controller = controller_with_cells(&[(1, 1), (5, 5)]);
+        let whole = controller.export_grid();
+        controller.handle_pointer_event(PointerGridEvent::SelectStart { cell: (0, 0) });
+        controller.handle_pointer_event(PointerGridEvent::DragEnd { cell: (3, 1) });
+
+        let selected = controller.export_grid();
+
+        assert_eq!((whole.width(), whole.height()), (6, 6));
+        assert_eq!((selected.width(), selected.height()), (4, 2));
+        assert_eq!(selected.live_cells().collect::<Vec<_>>(), vec![(1, 1)]);
+    }
+
+    #[test]
+    fn test_selection_operations() {
+        let mut controller = controller_with_cells(&[(1, 1), (5, 5)]);
+        controller.handle_pointer_event(PointerGridEvent::SelectStart { cell: (0, 0) });
+        controller.handle_pointer_event(PointerGridEvent::DragEnd { cell: (2, 1) });
+
+        let copied = controller.selection_pattern();
+        controller.invert_selection();
+        let inverted = controller.game.grid.live_cells().count();
+        let cut = controller.cut_selection();
+        controller.fill_selection();
+        controller.crop_to_selection();
+
+        assert_eq!(copied, Some(Pattern::new(vec![(1, 1)])));
+        assert_eq!(inverted, 5 + 1);
+        assert_eq!(cut.map(|p| p.population()), Some(5));
+        assert_eq!(
+            (controller.game.grid.width(), controller.game.grid.height()),
+            (3, 2)
+        );
+        assert_eq!(controller.game.grid.live_cells().count(), 6);
+        assert!(controller.selection().is_none());
+    }
+
+    #[test]
+    fn test_resizing_keeps_generation_and_can_be_undone() {
+        let mut controller = controller_with_cells(&[(1, 2), (2, 2), (3, 2)]);
+        controller.tick();
+        let before = controller.game.grid.live_cells().collect::<Vec<_>>();
+
+        controller.resize_grid(8, 8, Anchor::BottomRight);
+        let resized = (controller.game.grid.width(), controller.game.generation());
+        controller.undo();
+
+        assert_eq!(resized, (8, 1));
+        assert_eq!(controller.game.grid.width(), 6);
+        assert_eq!(
+            controller.game.grid.live_cells().collect::<Vec<_>>(),
+            before
+        );
+    }
+
+    #[test]
+    fn test_crop_to_live_needs_live_cells() {
+        let mut controller = controller_with_cells(&[(2, 2), (3, 2), (4, 2)]);
+
+        let cropped = controller.crop_to_live(1);
+        controller.clear();
+
+        assert!(cropped);
+        assert_eq!(
+            (controller.game.grid.width(), controller.game.grid.height()),
+            (5, 3)
+        );
+        assert!(!controller.crop_to_live(1));
+    }
+
+    #[test]
+    fn test_randomize_selection_only() {
+        let mut controller = controller_with_cells(&[]);
+        controller.handle_pointer_event(PointerGridEvent::SelectStart { cell: (1, 1) });
+        controller.handle_pointer_event(PointerGridEvent::DragEnd { cell: (2, 3) });
+        let options = SoupOptions {
+            density: 1.0,
+            ..SoupOptions::default()
+        };
+
+        controller.randomize(&options);
+
+        assert_eq!(controller.game.grid.live_cells().count(), 6);
+        controller.undo();
+        assert_eq!(controller.game.grid.live_cells().count(), 0);
+    }
+
+    #[test]
+    fn test_transform_selection_follows_cells() {
+        let mut controller = controller_with_cells(&[(1, 2), (2, 2), (3, 2)]);
+        controller.handle_pointer_event(PointerGridEvent::SelectStart { cell: (1, 2) });
+        controller.handle_pointer_event(PointerGridEvent::DragEnd { cell: (3, 2) });
+
+        controller.transform_selection(Transform::Rotate270);
+
+        assert_eq!(
+            controller.selection(),
+            Some(Region::from_corners((2, 1), (2, 3)))
+        );
+        assert_eq!(
+            controller.game.grid.live_cells().collect::<Vec<_>>(),
+            vec![(2, 1), (2, 2), (2, 3)]
+        );
+    }
+
+    #[test]
+    fn test_drawing_keeps_playing_but_pasting_pauses() {
+        let mut controller = controller_with_cells(&[]);
+        controller.tool = Tool::Rectangle;
+        controller.play();
+
+        controller.handle_pointer_event(PointerGridEvent::LeftClick { cell: (0, 0) });
+        controller.handle_pointer_event(PointerGridEvent::DragEnd { cell: (2, 2) });
+        let playing_after_drawing = controller.is_playing();
+        controller.start_paste(Pattern::new(vec![(0, 0)]));
+        controller.handle_pointer_event(PointerGridEvent::LeftClick { cell: (5, 5) });
+
+        assert!(playing_after_drawing);
+        assert!(!controller.is_playing());
+        assert_eq!(controller.game.grid.live_cells().count(), 8 + 1);
+    }
+
+    #[test]
+    fn test_brush_stroke_connects_fast_drags() {
+        let mut controller = controller_with_cells(&[]);
+        controller.tool = Tool::Brush;
+
+        controller.begin_stroke();
+        controller.handle_pointer_event(PointerGridEvent::LeftClick { cell: (0, 0) });
+        controller.handle_pointer_event(PointerGridEvent::DragMove { cell: (5, 0) });
+        controller.handle_pointer_event(PointerGridEvent::DragEnd { cell: (5, 3) });
+        controller.end_stroke();
+
+        assert_eq!(controller.game.grid.live_cells().count(), 6 + 3);
+        controller.undo();
+        assert_eq!(controller.game.grid.live_cells().count(), 0);
+    }
+
+    #[test]
+    fn test_shape_drawn_on_release_with_preview_while_dragging() {
+        let mut controller = controller_with_cells(&[]);
+        controller.tool = Tool::Rectangle;
+
+        controller.handle_pointer_event(PointerGridEvent::LeftClick { cell: (1, 1) });
+        controller.handle_pointer_event(PointerGridEvent::DragMove { cell: (3, 3) });
+        let preview = controller.preview().unwrap();
+        let drawn_before_release = controller.game.grid.live_cells().count();
+        controller.handle_pointer_event(PointerGridEvent::DragEnd { cell: (3, 3) });
+
+        assert_eq!(drawn_before_release, 0);
+        assert_eq!(preview.population(), 8);
+        assert_eq!(preview.bounds().map(|b| (b.min_x, b.min_y)), Some((-2, -2)));
+        assert_eq!(controller.game.grid.live_cells().count(), 8);
+        assert!(!controller.game.grid.get_cell_at_coord((2, 2)).unwrap());
+    }
+
+    #[test]
+    fn test_handle_pointer_event_right_drag_erases_stroke() {
+        let cells = (0..6).map(|x| (x, 3)).collect::<Vec<_>>();
+        let mut controller = controller_with_cells(&cells);
+
+        controller.handle_pointer_event(PointerGridEvent::RightClick { cell: (0, 3) });
+        controller.handle_pointer_event(PointerGridEvent::DragMove { cell: (4, 3) });
+        controller.handle_pointer_event(PointerGridEvent::DragEnd { cell: (4, 3) });
+        controller.handle_pointer_event(PointerGridEvent::DragMove { cell: (5, 3) });
+
+        assert_eq!(
+            controller.game.grid.live_cells().collect::<Vec<_>>(),
+            vec![(5, 3)]
+        );
+    }
+
+    #[test]
+    fn test_handle_pointer_event_uses_remapped_bindings() {
+        let mut controller = controller_with_cells(&[(0, 0)]);
+        controller.bindings = PointerBindings {
+            right: PointerAction::Toggle,
+            both: PointerAction::Nothing,
+        };
+
+        controller.handle_pointer_event(PointerGridEvent::RightClick { cell: (1, 1) });
+        controller.handle_pointer_event(PointerGridEvent::BothClick { cell: (0, 0) });
+
+        assert_eq!(
+            controller.game.grid.live_cells().collect::<Vec<_>>(),
+            vec![(0, 0), (1, 1)]
+        );
     }
 }
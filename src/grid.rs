@@ -1,5 +1,111 @@
 use std::fmt::Display;
 
+use crate::{
+    pattern::Transform,
+    soup::{SoupOptions, SplitMix64},
+};
+
+/// A rectangle of cells with `(x, y)` as its top-left corner.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Region {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Region {
+    /// Smallest region containing both cells, given in any order.
+    pub fn from_corners(a: (usize, usize), b: (usize, usize)) -> Region {
+        let (x, y) = (a.0.min(b.0), a.1.min(b.1));
+        Region {
+            x,
+            y,
+            width: a.0.max(b.0) - x + 1,
+            height: a.1.max(b.1) - y + 1,
+        }
+    }
+
+    pub fn contains(&self, (x, y): (usize, usize)) -> bool {
+        (self.x..self.x + self.width).contains(&x) && (self.y..self.y + self.height).contains(&y)
+    }
+
+    pub fn union(&self, other: Region) -> Region {
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+        Region {
+            x,
+            y,
+            width: (self.x + self.width).max(other.x + other.width) - x,
+            height: (self.y + self.height).max(other.y + other.height) - y,
+        }
+    }
+}
+
+/// Which part of the grid stays put when it is resized.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Anchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    #[default]
+    Centre,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl Anchor {
+    /// In reading order, as laid out in a 3x3 picker.
+    pub const ALL: [Anchor; 9] = [
+        Anchor::TopLeft,
+        Anchor::Top,
+        Anchor::TopRight,
+        Anchor::Left,
+        Anchor::Centre,
+        Anchor::Right,
+        Anchor::BottomLeft,
+        Anchor::Bottom,
+        Anchor::BottomRight,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Anchor::TopLeft => "Top left",
+            Anchor::Top => "Top",
+            Anchor::TopRight => "Top right",
+            Anchor::Left => "Left",
+            Anchor::Centre => "Centre",
+            Anchor::Right => "Right",
+            Anchor::BottomLeft => "Bottom left",
+            Anchor::Bottom => "Bottom",
+            Anchor::BottomRight => "Bottom right",
+        }
+    }
+
+    /// How far existing cells move when the grid changes size.
+    fn offset(self, (old_w, old_h): (usize, usize), (new_w, new_h): (usize, usize)) -> (i64, i64) {
+        // Halves of the size change taken up on the left and top.
+        let (fx, fy) = match self {
+            Anchor::TopLeft => (0, 0),
+            Anchor::Top => (1, 0),
+            Anchor::TopRight => (2, 0),
+            Anchor::Left => (0, 1),
+            Anchor::Centre => (1, 1),
+            Anchor::Right => (2, 1),
+            Anchor::BottomLeft => (0, 2),
+            Anchor::Bottom => (1, 2),
+            Anchor::BottomRight => (2, 2),
+        };
+        (
+            (new_w as i64 - old_w as i64) * fx / 2,
+            (new_h as i64 - old_h as i64) * fy / 2,
+        )
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Grid {
     cells: Vec<bool>,
@@ -56,6 +162,217 @@ impl Grid {
         self.set_cell(self.coord_to_index(coord), !current)
     }
 
+    pub fn live_cells(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(|&(_, &alive)| alive)
+            .map(|(i, _)| self.index_to_coord(i))
+    }
+
+    /// Smallest region containing every live cell.
+    pub fn live_bounds(&self) -> Option<Region> {
+        self.live_cells()
+            .map(|(x, y)| Region {
+                x,
+                y,
+                width: 1,
+                height: 1,
+            })
+            .reduce(|a, b| a.union(b))
+    }
+
+    /// Copies the cells of a region into a new grid of the region's size.
+    pub fn cropped(&self, region: Region) -> Result<Grid, IndexGridError> {
+        self.check_region(region)?;
+
+        let mut cropped = Grid::new(region.width, region.height);
+        for y in 0..region.height {
+            let start = self.coord_to_index((region.x, region.y + y));
+            let row = &self.cells[start..start + region.width];
+            let target = cropped.coord_to_index((0, y));
+            cropped.cells[target..target + region.width].copy_from_slice(row);
+        }
+        Ok(cropped)
+    }
+
+    /// Returns a grid of the new size keeping the cells, which move as the
+    /// anchor dictates; cells that no longer fit are dropped.
+    pub fn resized(&self, width: usize, height: usize, anchor: Anchor) -> Grid {
+        let offset = anchor.offset((self.width, self.height), (width, height));
+        self.reframed(offset, width, height)
+    }
+
+    /// Moves every cell by `(dx, dy)`, dropping those pushed off the grid.
+    pub fn translated(&self, dx: i64, dy: i64) -> Grid {
+        self.reframed((dx, dy), self.width, self.height)
+    }
+
+    /// Crops to the live cells plus `margin` empty cells on every side, or
+    /// `None` when nothing is alive.
+    pub fn cropped_to_live(&self, margin: usize) -> Option<Grid> {
+        let bounds = self.live_bounds()?;
+        let offset = (
+            margin as i64 - bounds.x as i64,
+            margin as i64 - bounds.y as i64,
+        );
+        Some(self.reframed(
+            offset,
+            bounds.width + 2 * margin,
+            bounds.height + 2 * margin,
+        ))
+    }
+
+    fn reframed(&self, (dx, dy): (i64, i64), width: usize, height: usize) -> Grid {
+        let mut out = Grid::new(width, height);
+        for (x, y) in self.live_cells() {
+            let moved = (x as i64).checked_add(dx).zip((y as i64).checked_add(dy));
+            if let Some(coord) = moved.and_then(|c| out.checked_coord(c)) {
+                let index = out.coord_to_index(coord);
+                out.cells[index] = true;
+            }
+        }
+        out
+    }
+
+    /// Returns the whole grid transformed, with width and height swapped by
+    /// the transforms that turn it on its side.
+    pub fn transformed(&self, transform: Transform) -> Grid {
+        let mut out = if transform.swaps_axes() {
+            Grid::new(self.height, self.width)
+        } else {
+            Grid::new(self.width, self.height)
+        };
+
+        // Opposite corners of the frame bound it under any of the symmetries.
+        let (ax, ay) = transform.apply((0, 0));
+        let (bx, by) = transform.apply((self.width as i64 - 1, self.height as i64 - 1));
+        let (dx, dy) = (-ax.min(bx), -ay.min(by));
+
+        for (x, y) in self.live_cells() {
+            let (tx, ty) = transform.apply((x as i64, y as i64));
+            out.set_cell_at_coord(((tx + dx) as usize, (ty + dy) as usize), true)
+                .expect("transformed: cell outside of new grid");
+        }
+        out
+    }
+
+    /// Transforms the cells of a region in place. A region that changes shape
+    /// keeps its centre; cells landing outside the grid are dropped. Returns
+    /// the region now holding the cells, clipped to the grid.
+    pub fn transform_region(
+        &mut self,
+        region: Region,
+        transform: Transform,
+    ) -> Result<Region, IndexGridError> {
+        let moved = self.cropped(region)?.transformed(transform);
+        self.fill_region(region, false)?;
+
+        let x = region.x as i64 + (region.width as i64 - moved.width as i64) / 2;
+        let y = region.y as i64 + (region.height as i64 - moved.height as i64) / 2;
+        for (cx, cy) in moved.live_cells() {
+            if let Some(coord) = self.checked_coord((x + cx as i64, y + cy as i64)) {
+                self.set_cell_at_coord(coord, true)?;
+            }
+        }
+
+        let left = x.max(0);
+        let top = y.max(0);
+        let right = (x + moved.width as i64).min(self.width as i64);
+        let bottom = (y + moved.height as i64).min(self.height as i64);
+        Ok(Region {
+            x: left as usize,
+            y: top as usize,
+            width: (right - left) as usize,
+            height: (bottom - top) as usize,
+        })
+    }
+
+    /// Fills the whole grid with a random soup.
+    pub fn randomize(&mut self, options: &SoupOptions) {
+        let region = Region {
+            x: 0,
+            y: 0,
+            width: self.width,
+            height: self.height,
+        };
+        self.randomize_region(region, options)
+            .expect("randomize: whole grid is in bounds");
+    }
+
+    /// Fills the region with a random soup. Cells related by the symmetry
+    /// about the region's centre share one random draw; images falling outside
+    /// a non-square region are skipped.
+    pub fn randomize_region(
+        &mut self,
+        region: Region,
+        options: &SoupOptions,
+    ) -> Result<(), IndexGridError> {
+        self.check_region(region)?;
+
+        let (w, h) = (region.width as i64, region.height as i64);
+        let mut done = vec![false; region.width * region.height];
+        let mut rng = SplitMix64::new(options.seed);
+
+        for i in 0..done.len() {
+            if done[i] {
+                continue;
+            }
+            let alive = rng.chance(options.density);
+
+            // Work in doubled coordinates centred on the region so half-cell
+            // centres stay integral.
+            let (x, y) = ((i % region.width) as i64, (i / region.width) as i64);
+            let centred = (2 * x - (w - 1), 2 * y - (h - 1));
+            for transform in options.symmetry.transforms() {
+                let (tx, ty) = transform.apply(centred);
+                let (dx, dy) = (tx + w - 1, ty + h - 1);
+                if dx % 2 != 0
+                    || dy % 2 != 0
+                    || !(0..2 * w).contains(&dx)
+                    || !(0..2 * h).contains(&dy)
+                {
+                    continue;
+                }
+
+                let (ix, iy) = ((dx / 2) as usize, (dy / 2) as usize);
+                done[iy * region.width + ix] = true;
+                let index = self.coord_to_index((region.x + ix, region.y + iy));
+                self.cells[index] = alive;
+            }
+        }
+        Ok(())
+    }
+
+    /// Sets every cell of the region to `value`.
+    pub fn fill_region(&mut self, region: Region, value: bool) -> Result<(), IndexGridError> {
+        self.check_region(region)?;
+        for y in region.y..region.y + region.height {
+            let start = self.coord_to_index((region.x, y));
+            self.cells[start..start + region.width].fill(value);
+        }
+        Ok(())
+    }
+
+    /// Flips every cell of the region between alive and dead.
+    pub fn invert_region(&mut self, region: Region) -> Result<(), IndexGridError> {
+        self.check_region(region)?;
+        for y in region.y..region.y + region.height {
+            let start = self.coord_to_index((region.x, y));
+            for cell in &mut self.cells[start..start + region.width] {
+                *cell = !*cell;
+            }
+        }
+        Ok(())
+    }
+
+    fn check_region(&self, region: Region) -> Result<(), IndexGridError> {
+        if region.x + region.width > self.width || region.y + region.height > self.height {
+            return Err(IndexGridError::IndexOutOfBounds);
+        }
+        Ok(())
+    }
+
     fn set_cell(&mut self, i: usize, value: bool) -> Result<(), IndexGridError> {
         if !self.is_index_inbounds(i) {
             return Err(IndexGridError::IndexOutOfBounds);
@@ -68,6 +385,12 @@ impl Grid {
         (i % self.width, i / self.width)
     }
 
+    /// Converts a possibly negative coordinate, or `None` if it lies outside the grid.
+    pub fn checked_coord(&self, (x, y): (i64, i64)) -> Option<(usize, usize)> {
+        let inside = (0..self.width as i64).contains(&x) && (0..self.height as i64).contains(&y);
+        inside.then_some((x as usize, y as usize))
+    }
+
     fn coord_to_index(&self, coord: (usize, usize)) -> usize {
         let (x, y) = coord;
         y * self.width + x
@@ -145,6 +468,7 @@ pub enum IndexGridError {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::soup::Symmetry;
 
     #[test]
     fn test_creating_empty_grid() {
@@ -242,6 +566,282 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_listing_live_cells() {
+        let mut grid = Grid::new(3, 3);
+
+        let _ = grid.set_cell_at_coord((2, 0), true);
+        let _ = grid.set_cell_at_coord((1, 2), true);
+
+        assert_eq!(grid.live_cells().collect::<Vec<_>>(), vec![(2, 0), (1, 2)]);
+    }
+
+    #[test]
+    fn test_live_bounds() {
+        let mut grid = Grid::new(6, 6);
+        assert_eq!(grid.live_bounds(), None);
+
+        let _ = grid.set_cell_at_coord((4, 1), true);
+        let _ = grid.set_cell_at_coord((2, 3), true);
+
+        assert_eq!(
+            grid.live_bounds(),
+            Some(Region {
+                x: 2,
+                y: 1,
+                width: 3,
+                height: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_cropping_region() {
+        let mut grid = Grid::new(4, 4);
+        let _ = grid.set_cell_at_coord((2, 1), true);
+        let _ = grid.set_cell_at_coord((3, 3), true);
+        let region = Region {
+            x: 1,
+            y: 1,
+            width: 2,
+            height: 2,
+        };
+
+        let cropped = grid.cropped(region).unwrap();
+
+        assert_eq!((cropped.width(), cropped.height()), (2, 2));
+        assert_eq!(cropped.live_cells().collect::<Vec<_>>(), vec![(1, 0)]);
+    }
+
+    #[test]
+    fn test_cropping_region_out_of_bounds() {
+        let grid = Grid::new(4, 4);
+        let region = Region {
+            x: 3,
+            y: 0,
+            width: 2,
+            height: 1,
+        };
+
+        assert!(grid.cropped(region).is_err());
+    }
+
+    #[test]
+    fn test_region_from_corners() {
+        let region = Region::from_corners((4, 1), (2, 3));
+
+        assert_eq!(
+            region,
+            Region {
+                x: 2,
+                y: 1,
+                width: 3,
+                height: 3
+            }
+        );
+        assert!(region.contains((4, 3)));
+        assert!(!region.contains((5, 3)));
+    }
+
+    #[test]
+    fn test_filling_and_inverting_region() {
+        let mut grid = Grid::new(4, 3);
+        let _ = grid.set_cell_at_coord((1, 1), true);
+        let region = Region::from_corners((1, 0), (2, 1));
+
+        grid.invert_region(region).unwrap();
+        let inverted = grid.live_cells().collect::<Vec<_>>();
+        grid.fill_region(region, false).unwrap();
+
+        assert_eq!(inverted, vec![(1, 0), (2, 0), (2, 1)]);
+        assert_eq!(grid.live_cells().count(), 0);
+        assert!(
+            grid.fill_region(Region::from_corners((3, 2), (4, 2)), true)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_transforming_whole_grid() {
+        let mut grid = Grid::new(3, 2);
+        let _ = grid.set_cell_at_coord((0, 0), true);
+        let _ = grid.set_cell_at_coord((2, 1), true);
+
+        let rotated = grid.transformed(Transform::Rotate90);
+        let flipped = grid.transformed(Transform::FlipHorizontal);
+        let transposed = grid.transformed(Transform::Transpose);
+        let round_trip = rotated.transformed(Transform::Rotate270);
+
+        assert_eq!((rotated.width(), rotated.height()), (2, 3));
+        assert_eq!(
+            rotated.live_cells().collect::<Vec<_>>(),
+            vec![(1, 0), (0, 2)]
+        );
+        assert_eq!(
+            flipped.live_cells().collect::<Vec<_>>(),
+            vec![(2, 0), (0, 1)]
+        );
+        assert_eq!(
+            transposed.live_cells().collect::<Vec<_>>(),
+            vec![(0, 0), (1, 2)]
+        );
+        assert_eq!(
+            round_trip.live_cells().collect::<Vec<_>>(),
+            grid.live_cells().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_rotating_region_grows_around_centre() {
+        let mut grid = Grid::new(5, 5);
+        for x in 1..4 {
+            let _ = grid.set_cell_at_coord((x, 2), true);
+        }
+        let _ = grid.set_cell_at_coord((0, 0), true);
+
+        let region = grid
+            .transform_region(Region::from_corners((1, 2), (3, 2)), Transform::Rotate90)
+            .unwrap();
+
+        assert_eq!(region, Region::from_corners((2, 1), (2, 3)));
+        assert_eq!(
+            grid.live_cells().collect::<Vec<_>>(),
+            vec![(0, 0), (2, 1), (2, 2), (2, 3)]
+        );
+    }
+
+    #[test]
+    fn test_rotating_region_clips_at_edge() {
+        let mut grid = Grid::new(4, 3);
+        for x in 0..4 {
+            let _ = grid.set_cell_at_coord((x, 0), true);
+        }
+
+        let region = grid
+            .transform_region(Region::from_corners((0, 0), (3, 0)), Transform::Rotate90)
+            .unwrap();
+
+        assert_eq!(region, Region::from_corners((1, 0), (1, 2)));
+        assert_eq!(grid.live_cells().count(), 3);
+    }
+
+    #[test]
+    fn test_resizing_keeps_cells_around_anchor() {
+        let mut grid = Grid::new(4, 4);
+        grid.set_cell_at_coord((0, 0), true).unwrap();
+        grid.set_cell_at_coord((3, 3), true).unwrap();
+
+        let grown = grid.resized(8, 6, Anchor::Centre);
+        let shrunk = grid.resized(2, 2, Anchor::BottomRight);
+        let corner = grid.resized(6, 6, Anchor::TopLeft);
+
+        assert_eq!((grown.width(), grown.height()), (8, 6));
+        assert_eq!(grown.live_cells().collect::<Vec<_>>(), vec![(2, 1), (5, 4)]);
+        assert_eq!(shrunk.live_cells().collect::<Vec<_>>(), vec![(1, 1)]);
+        assert_eq!(
+            corner.live_cells().collect::<Vec<_>>(),
+            vec![(0, 0), (3, 3)]
+        );
+    }
+
+    #[test]
+    fn test_translating_drops_cells_off_grid() {
+        let mut grid = Grid::new(4, 4);
+        grid.set_cell_at_coord((0, 1), true).unwrap();
+        grid.set_cell_at_coord((3, 1), true).unwrap();
+
+        let moved = grid.translated(-1, 2);
+
+        assert_eq!(moved.live_cells().collect::<Vec<_>>(), vec![(2, 3)]);
+        assert_eq!(grid.translated(i64::MAX, i64::MIN).live_cells().count(), 0);
+    }
+
+    #[test]
+    fn test_cropping_to_live_cells_with_margin() {
+        let mut grid = Grid::new(10, 10);
+        grid.set_cell_at_coord((4, 3), true).unwrap();
+        grid.set_cell_at_coord((6, 3), true).unwrap();
+
+        let cropped = grid.cropped_to_live(2).unwrap();
+
+        assert_eq!((cropped.width(), cropped.height()), (7, 5));
+        assert_eq!(
+            cropped.live_cells().collect::<Vec<_>>(),
+            vec![(2, 2), (4, 2)]
+        );
+        assert!(Grid::new(3, 3).cropped_to_live(1).is_none());
+    }
+
+    #[test]
+    fn test_randomizing_is_reproducible() {
+        let options = SoupOptions {
+            seed: 1234,
+            ..SoupOptions::default()
+        };
+        let mut a = Grid::new(16, 16);
+        let mut b = Grid::new(16, 16);
+
+        a.randomize(&options);
+        b.randomize(&options);
+
+        assert_eq!(
+            a.live_cells().collect::<Vec<_>>(),
+            b.live_cells().collect::<Vec<_>>()
+        );
+        assert!((64..192).contains(&a.live_cells().count()));
+    }
+
+    #[test]
+    fn test_randomizing_region_with_extreme_densities() {
+        let mut grid = Grid::new(5, 5);
+        let region = Region::from_corners((1, 1), (3, 2));
+
+        grid.randomize_region(
+            region,
+            &SoupOptions {
+                density: 1.0,
+                ..SoupOptions::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(grid.live_cells().count(), 6);
+        assert!(grid.live_cells().all(|c| region.contains(c)));
+    }
+
+    #[test]
+    fn test_randomizing_with_symmetry() {
+        for symmetry in Symmetry::ALL {
+            let options = SoupOptions {
+                seed: 99,
+                symmetry,
+                ..SoupOptions::default()
+            };
+            let mut grid = Grid::new(9, 9);
+
+            grid.randomize(&options);
+
+            for &transform in symmetry.transforms() {
+                assert_eq!(
+                    grid.transformed(transform).live_cells().collect::<Vec<_>>(),
+                    grid.live_cells().collect::<Vec<_>>(),
+                    "{} soup changed by {transform:?}",
+                    symmetry.name()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_checking_signed_coordinates() {
+        let grid = Grid::new(3, 2);
+
+        assert_eq!(grid.checked_coord((2, 1)), Some((2, 1)));
+        assert_eq!(grid.checked_coord((-1, 0)), None);
+        assert_eq!(grid.checked_coord((3, 0)), None);
+        assert_eq!(grid.checked_coord((0, 2)), None);
+    }
+
     #[test]
     fn test_count_living_neighbors() {
         let mut grid = Grid::new(3, 3);
@@ -0,0 +1,243 @@
+use std::{collections::VecDeque, ops::RangeInclusive};
+
+use crate::{game::Game, snapshot::Snapshot};
+
+/// Default memory budget for keyframes and populations.
+pub const DEFAULT_MAX_BYTES: usize = 64 * 1024 * 1024;
+
+/// Generations between keyframes when the game runs unedited.
+pub const KEYFRAME_INTERVAL: u32 = 32;
+
+/// The generations a game has been through, so any of them can be revisited.
+/// Keeps a snapshot every few generations and after each edit, replaying
+/// from the nearest one to rebuild the rest, plus every generation's
+/// population. The oldest keyframes are forgotten once over the memory budget.
+#[derive(Debug)]
+pub struct GenerationHistory {
+    /// Ascending by generation; the first is always at `start`.
+    keyframes: VecDeque<Snapshot>,
+    /// Population of each generation from `start` on.
+    populations: Vec<u32>,
+    start: u32,
+    bytes: usize,
+    max_bytes: usize,
+    interval: u32,
+}
+
+impl Default for GenerationHistory {
+    fn default() -> Self {
+        GenerationHistory::new(DEFAULT_MAX_BYTES, KEYFRAME_INTERVAL)
+    }
+}
+
+impl GenerationHistory {
+    pub fn new(max_bytes: usize, interval: u32) -> Self {
+        GenerationHistory {
+            keyframes: VecDeque::new(),
+            populations: Vec::new(),
+            start: 0,
+            bytes: 0,
+            max_bytes,
+            interval: interval.max(1),
+        }
+    }
+
+    /// Notes the game's current generation. An `edited` game, or one outside
+    /// what is recorded, starts a new keyframe and forgets everything after
+    /// it, forking the history; a generation already recorded is left alone.
+    pub fn record(&mut self, game: &Game, edited: bool) {
+        let generation = game.generation();
+        let population = game.grid.live_cells().count() as u32;
+
+        match self.range() {
+            Some(range) if !edited && range.contains(&generation) => return,
+            Some(range) if !edited && generation == range.end() + 1 => {
+                self.push_population(population);
+                let last_keyframe = self.keyframes.back().map_or(0, Snapshot::generation);
+                if generation - last_keyframe >= self.interval {
+                    self.push_keyframe(game);
+                }
+            }
+            Some(range) if generation > *range.start() && generation <= range.end() + 1 => {
+                self.truncate_from(generation);
+                self.push_population(population);
+                self.push_keyframe(game);
+            }
+            _ => {
+                self.clear();
+                self.start = generation;
+                self.push_population(population);
+                self.push_keyframe(game);
+            }
+        }
+        self.trim();
+    }
+
+    /// Rebuilds a recorded generation from the keyframe at or before it.
+    pub fn restore(&self, generation: u32) -> Option<Game> {
+        if !self.range()?.contains(&generation) {
+            return None;
+        }
+
+        let index = self
+            .keyframes
+            .partition_point(|k| k.generation() <= generation)
+            .checked_sub(1)?;
+        let mut game = self.keyframes[index].restore();
+        while game.generation() < generation {
+            game.tick();
+        }
+        Some(game)
+    }
+
+    /// First and last generations that can be restored.
+    pub fn range(&self) -> Option<RangeInclusive<u32>> {
+        let len = self.populations.len() as u32;
+        (len > 0).then(|| self.start..=self.start + len - 1)
+    }
+
+    /// Population of every generation in [`Self::range`], in order.
+    pub fn populations(&self) -> &[u32] {
+        &self.populations
+    }
+
+    pub fn keyframe_generations(&self) -> impl Iterator<Item = u32> + '_ {
+        self.keyframes.iter().map(Snapshot::generation)
+    }
+
+    pub fn clear(&mut self) {
+        self.keyframes.clear();
+        self.populations.clear();
+        self.start = 0;
+        self.bytes = 0;
+    }
+
+    fn push_population(&mut self, population: u32) {
+        self.populations.push(population);
+        self.bytes += size_of::<u32>();
+    }
+
+    fn push_keyframe(&mut self, game: &Game) {
+        let snapshot = Snapshot::capture(game);
+        self.bytes += snapshot.size_bytes();
+        self.keyframes.push_back(snapshot);
+    }
+
+    /// Forgets `generation` and everything after it.
+    fn truncate_from(&mut self, generation: u32) {
+        while self
+            .keyframes
+            .back()
+            .is_some_and(|k| k.generation() >= generation)
+        {
+            let dropped = self.keyframes.pop_back().expect("truncate_from: checked");
+            self.bytes -= dropped.size_bytes();
+        }
+        let kept = (generation - self.start) as usize;
+        self.bytes -= (self.populations.len() - kept) * size_of::<u32>();
+        self.populations.truncate(kept);
+    }
+
+    /// Drops the oldest keyframes, and the populations before the next one,
+    /// until back under budget.
+    fn trim(&mut self) {
+        while self.bytes > self.max_bytes && self.keyframes.len() > 1 {
+            let oldest = self.keyframes.pop_front().expect("trim: checked");
+            self.bytes -= oldest.size_bytes();
+
+            let next = self.keyframes[0].generation();
+            let dropped = (next - self.start) as usize;
+            self.populations.drain(..dropped);
+            self.bytes -= dropped * size_of::<u32>();
+            self.start = next;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+
+    fn glider() -> Game {
+        let mut grid = Grid::new(12, 12);
+        for cell in [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)] {
+            grid.set_cell_at_coord(cell, true).unwrap();
+        }
+        Game::new(grid)
+    }
+
+    /// Runs the game for `generations`, recording each one.
+    fn run(history: &mut GenerationHistory, game: &mut Game, generations: u32) {
+        history.record(game, false);
+        for _ in 0..generations {
+            game.tick();
+            history.record(game, false);
+        }
+    }
+
+    fn live_cells(game: &Game) -> Vec<(usize, usize)> {
+        game.grid.live_cells().collect()
+    }
+
+    #[test]
+    fn test_restoring_replays_from_nearest_keyframe() {
+        let mut history = GenerationHistory::new(DEFAULT_MAX_BYTES, 4);
+        let mut game = glider();
+        run(&mut history, &mut game, 10);
+        let mut expected = glider();
+        for _ in 0..7 {
+            expected.tick();
+        }
+
+        let restored = history.restore(7).unwrap();
+
+        assert_eq!(history.range(), Some(0..=10));
+        assert_eq!(
+            history.keyframe_generations().collect::<Vec<_>>(),
+            vec![0, 4, 8]
+        );
+        assert_eq!(restored.generation(), 7);
+        assert_eq!(live_cells(&restored), live_cells(&expected));
+        assert_eq!(history.populations(), &[5; 11]);
+        assert!(history.restore(11).is_none());
+    }
+
+    #[test]
+    fn test_edit_forks_history() {
+        let mut history = GenerationHistory::new(DEFAULT_MAX_BYTES, 4);
+        let mut game = glider();
+        run(&mut history, &mut game, 10);
+        let mut edited = history.restore(5).unwrap();
+        edited.grid.set_cell_at_coord((11, 11), true).unwrap();
+
+        history.record(&edited, true);
+        let restored = history.restore(5).unwrap();
+
+        assert_eq!(history.range(), Some(0..=5));
+        assert_eq!(
+            history.keyframe_generations().collect::<Vec<_>>(),
+            vec![0, 4, 5]
+        );
+        assert_eq!(live_cells(&restored), live_cells(&edited));
+        assert_eq!(history.populations()[5], 6);
+    }
+
+    #[test]
+    fn test_oldest_keyframes_dropped_over_budget() {
+        // A block near the corner keeps one word and its run header per
+        // keyframe, and every generation costs four bytes of population.
+        let mut history = GenerationHistory::new(3 * (size_of::<Snapshot>() + 16) + 12 * 4, 4);
+        let mut grid = Grid::new(10, 10);
+        for cell in [(1, 1), (2, 1), (1, 2), (2, 2)] {
+            grid.set_cell_at_coord(cell, true).unwrap();
+        }
+        let mut game = Game::new(grid);
+
+        run(&mut history, &mut game, 16);
+
+        assert_eq!(history.range(), Some(8..=16));
+        assert!(history.restore(7).is_none());
+        assert_eq!(history.restore(9).map(|g| g.generation()), Some(9));
+    }
+}
@@ -0,0 +1,325 @@
+use std::{
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    formats::{FormatError, PatternFormat, rle},
+    pattern::Pattern,
+};
+
+/// Category given to patterns sitting directly in the user folder.
+pub const USER_CATEGORY: &str = "User";
+
+/// Largest built-in pattern; the glider gun is the widest at 36 cells.
+const BUILTIN_MAX_SIZE: (usize, usize) = (64, 64);
+
+/// Classic patterns shipped with the app, as (category, name, RLE).
+const BUILTIN: &[(&str, &str, &str)] = &[
+    ("Still lifes", "Block", "2o$2o!"),
+    ("Still lifes", "Beehive", "b2o$o2bo$b2o!"),
+    ("Still lifes", "Loaf", "b2o$o2bo$bobo$2bo!"),
+    ("Still lifes", "Boat", "2o$obo$bo!"),
+    ("Still lifes", "Tub", "bo$obo$bo!"),
+    ("Oscillators", "Blinker", "3o!"),
+    ("Oscillators", "Toad", "b3o$3o!"),
+    ("Oscillators", "Beacon", "2o$2o$2b2o$2b2o!"),
+    (
+        "Oscillators",
+        "Pulsar",
+        "2b3o3b3o2$o4bobo4bo$o4bobo4bo$o4bobo4bo$2b3o3b3o2$2b3o3b3o$o4bobo4bo$\
+         o4bobo4bo$o4bobo4bo2$2b3o3b3o!",
+    ),
+    ("Oscillators", "Pentadecathlon", "2bo4bo$2ob4ob2o$2bo4bo!"),
+    ("Spaceships", "Glider", "bo$2bo$3o!"),
+    ("Spaceships", "LWSS", "bo2bo$o$o3bo$4o!"),
+    ("Spaceships", "MWSS", "3bo$bo3bo$o$o4bo$5o!"),
+    ("Spaceships", "HWSS", "3b2o$bo4bo$o$o5bo$6o!"),
+    (
+        "Guns",
+        "Gosper glider gun",
+        "24bo$22bobo$12b2o6b2o12b2o$11bo3bo4b2o12b2o$2o8bo5bo3b2o$2o8bo3bob2o4b\
+         obo$10bo5bo7bo$11bo3bo$12b2o!",
+    ),
+    ("Methuselahs", "R-pentomino", "b2o$2o$bo!"),
+    ("Methuselahs", "Acorn", "bo$3bo$2o2b3o!"),
+    ("Methuselahs", "Diehard", "6bo$2o$bo3b3o!"),
+    ("Eaters", "Eater 1", "2o$obo$2bo$2b2o!"),
+    (
+        "Eaters",
+        "Eater 2",
+        "4bo$3bobo$3bobo$b3ob2o$o$b3ob2o$3bob2o!",
+    ),
+];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LibraryEntry {
+    pub category: String,
+    pub name: String,
+    pub pattern: Pattern,
+}
+
+/// The built-in patterns plus any loaded from the user's folder.
+#[derive(Clone, Debug)]
+pub struct Library {
+    builtin: Vec<LibraryEntry>,
+    user: Vec<LibraryEntry>,
+}
+
+impl Default for Library {
+    fn default() -> Self {
+        let builtin = BUILTIN
+            .iter()
+            .map(|&(category, name, code)| LibraryEntry {
+                category: category.to_string(),
+                name: name.to_string(),
+                pattern: rle::parse(code, BUILTIN_MAX_SIZE)
+                    .expect("builtin library pattern is valid RLE"),
+            })
+            .collect();
+
+        Library {
+            builtin,
+            user: Vec::new(),
+        }
+    }
+}
+
+impl Library {
+    pub fn entries(&self) -> impl Iterator<Item = &LibraryEntry> {
+        self.builtin.iter().chain(&self.user)
+    }
+
+    /// Entries whose name or category contains the query, ignoring case.
+    pub fn search(&self, query: &str) -> impl Iterator<Item = &LibraryEntry> {
+        let query = query.trim().to_lowercase();
+        self.entries().filter(move |e| {
+            e.name.to_lowercase().contains(&query) || e.category.to_lowercase().contains(&query)
+        })
+    }
+
+    /// Replaces the user patterns with those found in `dir`, failing only
+    /// when the folder itself cannot be read.
+    pub fn load_user_dir(
+        &mut self,
+        dir: &Path,
+        max_size: (usize, usize),
+    ) -> Result<LoadedDir, LibraryError> {
+        let loaded = load_dir(dir, max_size)?;
+        self.user = loaded.entries.clone();
+        Ok(loaded)
+    }
+}
+
+/// The patterns read from a folder, and the files that had to be skipped.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LoadedDir {
+    pub entries: Vec<LibraryEntry>,
+    pub skipped: Vec<LibraryError>,
+}
+
+impl Display for LoadedDir {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "loaded {}", self.entries.len())?;
+        if !self.skipped.is_empty() {
+            write!(f, ", skipped {}:", self.skipped.len())?;
+            for (i, e) in self.skipped.iter().enumerate() {
+                let separator = if i == 0 { " " } else { "; " };
+                write!(f, "{separator}{e}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reads every pattern file in `dir`. Each subfolder is a category; files at
+/// the top level go under [`USER_CATEGORY`]. Files without a pattern
+/// extension are ignored, and those that cannot be read or parsed are skipped
+/// and listed rather than failing the whole folder.
+pub fn load_dir(dir: &Path, max_size: (usize, usize)) -> Result<LoadedDir, LibraryError> {
+    let mut loaded = LoadedDir::default();
+    let mut add = |result| match result {
+        Ok(entry) => loaded.entries.push(entry),
+        Err(e) => loaded.skipped.push(e),
+    };
+
+    for path in sorted_dir(dir)? {
+        if path.is_dir() {
+            let category = file_name(&path);
+            match sorted_dir(&path) {
+                Ok(files) => files
+                    .iter()
+                    .filter(|file| file.is_file() && PatternFormat::from_extension(file).is_some())
+                    .for_each(|file| add(load_file(file, &category, max_size))),
+                Err(e) => add(Err(e)),
+            }
+        } else if PatternFormat::from_extension(&path).is_some() {
+            add(load_file(&path, USER_CATEGORY, max_size));
+        }
+    }
+    Ok(loaded)
+}
+
+fn load_file(
+    path: &Path,
+    category: &str,
+    max_size: (usize, usize),
+) -> Result<LibraryEntry, LibraryError> {
+    let failed = |e| LibraryError::File(path.to_path_buf(), e);
+    let contents = fs::read_to_string(path).map_err(|e| failed(FormatError::Io(e.to_string())))?;
+    let format =
+        PatternFormat::detect(path, &contents).ok_or_else(|| failed(FormatError::UnknownFormat))?;
+    let pattern = format.parse(&contents, max_size).map_err(failed)?;
+
+    let name = path
+        .file_stem()
+        .map_or_else(String::new, |s| s.to_string_lossy().into_owned());
+    Ok(LibraryEntry {
+        category: category.to_string(),
+        name,
+        pattern,
+    })
+}
+
+fn sorted_dir(dir: &Path) -> Result<Vec<PathBuf>, LibraryError> {
+    let mut paths = fs::read_dir(dir)
+        .map_err(|e| LibraryError::Io(format!("{}: {e}", dir.display())))?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| LibraryError::Io(e.to_string()))?;
+    paths.sort();
+    Ok(paths)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map_or_else(String::new, |s| s.to_string_lossy().into_owned())
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LibraryError {
+    Io(String),
+    File(PathBuf, FormatError),
+}
+
+impl Display for LibraryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LibraryError::Io(message) => write!(f, "{message}"),
+            LibraryError::File(path, e) => write!(f, "{}: {e}", path.display()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Game;
+
+    fn builtin(name: &str) -> Pattern {
+        Library::default()
+            .entries()
+            .find(|e| e.name == name)
+            .map(|e| e.pattern.clone())
+            .unwrap()
+    }
+
+    /// Evolves the pattern in a roomy grid and returns its live cells.
+    fn evolve(pattern: &Pattern, generations: usize) -> Vec<(usize, usize)> {
+        let pattern = pattern.translated(20, 20);
+        let mut game = Game::new(pattern.to_grid(80, 80).unwrap());
+        for _ in 0..generations {
+            game.tick();
+        }
+        game.grid.live_cells().collect()
+    }
+
+    #[test]
+    fn test_builtin_patterns_behave() {
+        let library = Library::default();
+
+        for entry in library.entries() {
+            let period = match entry.category.as_str() {
+                "Still lifes" | "Eaters" => 1,
+                "Oscillators" if entry.name == "Pulsar" => 3,
+                "Oscillators" if entry.name == "Pentadecathlon" => 15,
+                "Oscillators" => 2,
+                _ => continue,
+            };
+            assert_eq!(
+                evolve(&entry.pattern, period),
+                evolve(&entry.pattern, 0),
+                "{} does not repeat",
+                entry.name
+            );
+        }
+        assert_eq!(builtin("Gosper glider gun").population(), 36);
+        assert_eq!(builtin("Acorn").population(), 7);
+    }
+
+    #[test]
+    fn test_searching_names_and_categories() {
+        let library = Library::default();
+
+        let gliders = library
+            .search("GLIDER")
+            .map(|e| e.name.as_str())
+            .collect::<Vec<_>>();
+        let eaters = library.search("eater").count();
+
+        assert_eq!(gliders, vec!["Glider", "Gosper glider gun"]);
+        assert_eq!(eaters, 2);
+    }
+
+    #[test]
+    fn test_loading_user_folder() {
+        let dir = std::env::temp_dir().join("petri_library_test");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("Puffers")).unwrap();
+        fs::write(dir.join("domino.cells"), "OO\n").unwrap();
+        fs::write(dir.join("notes.txt"), "not a pattern: see folder\n").unwrap();
+        fs::write(dir.join("Puffers").join("tiny.rle"), "x = 3, y = 1\n3o!\n").unwrap();
+        let mut library = Library::default();
+
+        let loaded = library.load_user_dir(&dir, (100, 100));
+        let user = library
+            .search("")
+            .filter(|e| e.category != "Still lifes")
+            .map(|e| (e.category.as_str(), e.name.as_str()))
+            .collect::<Vec<_>>();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(loaded.map(|l| l.entries.len()), Ok(2));
+        assert!(user.contains(&("Puffers", "tiny")));
+        assert!(user.contains(&(USER_CATEGORY, "domino")));
+    }
+
+    #[test]
+    fn test_loading_skips_bad_files() {
+        let dir = std::env::temp_dir().join("petri_library_bad_test");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("domino.cells"), "OO\n").unwrap();
+        fs::write(dir.join("broken.rle"), "x = 1, y = 1\noz!\n").unwrap();
+        fs::write(dir.join("binary.rle"), [0xff, 0xfe, 0x00]).unwrap();
+        fs::write(dir.join(".DS_Store"), [0x00, 0x01, 0xff]).unwrap();
+        let mut library = Library::default();
+
+        let loaded = library.load_user_dir(&dir, (100, 100)).unwrap();
+        let user = library
+            .entries()
+            .filter(|e| e.category == USER_CATEGORY)
+            .count();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(loaded.entries.len(), 1);
+        assert_eq!(user, 1);
+        assert_eq!(loaded.skipped.len(), 2);
+        assert!(loaded.to_string().starts_with("loaded 1, skipped 2: "));
+        assert!(matches!(
+            &loaded.skipped[1],
+            LibraryError::File(path, FormatError::Parse { line: 2, .. }) if path.ends_with("broken.rle")
+        ));
+    }
+}
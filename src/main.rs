@@ -1,15 +1,61 @@
-use std::time::Duration;
+use std::{
+    path::Path,
+    time::{Duration, Instant},
+};
 
+use animation::GifExport;
+use draw::{DrawSymmetry, Tool};
 use game::Game;
-use game_loop::{GameController, SystemClock};
+use game_loop::{GameController, RunGoal, RunOutcome, SystemClock};
 use grid::Grid;
-use ui::GridView;
+use pattern::{PasteMode, Pattern, Transform};
+use period::{PeriodSearch, SearchOutcome};
+use session::{Session, SessionError};
+use ui::{
+    GridView,
+    bookmarks::{BookmarkAction, BookmarksPanel},
+    gif_export::{GifExportAction, GifExportWindow},
+    identify::{IdentifyAction, IdentifyWindow},
+    image_file::{ImageFileAction, ImageFileWindow},
+    library::{LibraryAction, LibraryWindow},
+    pattern_file::{PatternFileAction, PatternFileWindow},
+    randomize::{RandomizeAction, RandomizeWindow},
+    resize::{ResizeAction, ResizeWindow},
+    run::{RunAction, RunWindow},
+    session_file::{SessionFileAction, SessionFileWindow},
+    settings::SettingsWindow,
+    timeline::TimelineView,
+};
 
+mod animation;
+mod apgcode;
+mod bitmap;
+mod draw;
+mod formats;
 mod game;
 mod game_loop;
 mod grid;
 mod grid_evolver;
+mod history;
+mod library;
+mod pattern;
+mod period;
+mod session;
+mod snapshot;
+mod soup;
+mod stability;
+mod svg;
 mod ui;
+mod undo;
+
+const MAX_WIDTH: usize = 500;
+const MAX_HEIGHT: usize = 300;
+const MIN_ZOOM: f32 = 0.5;
+const MAX_ZOOM: f32 = 4.0;
+/// Empty cells left around a pattern dropped on the setup screen.
+const DROP_MARGIN: usize = 16;
+/// Generations looked through for a repeat before giving up.
+const PERIOD_SEARCH_LIMIT: u32 = 1000;
 
 fn main() -> eframe::Result<()> {
     eframe::run_native(
@@ -29,18 +75,79 @@ struct MyApp {
 
 enum AppScreen {
     Setup(SetupState),
-    Playing(GameState),
+    Playing(Box<GameState>),
 }
 
 struct SetupState {
     width: String,
     height: String,
+    session_path: String,
 }
 
 struct GameState {
     controller: GameController<SystemClock>,
     scroll_offset: egui::Vec2,
     zoom: f32,
+    pattern_file: PatternFileWindow,
+    identify: IdentifyWindow,
+    image_file: ImageFileWindow,
+    gif_window: GifExportWindow,
+    gif_export: Option<GifExport>,
+    run: RunWindow,
+    /// When the running fast-forward last reported its progress.
+    run_reported: Instant,
+    /// The running period search, with what it looks at.
+    period_search: Option<(PeriodSearch, &'static str)>,
+    bookmarks: BookmarksPanel,
+    session_file: SessionFileWindow,
+    library: LibraryWindow,
+    randomize: RandomizeWindow,
+    resize: ResizeWindow,
+    settings: SettingsWindow,
+}
+
+impl GameState {
+    fn new(grid: Grid) -> Self {
+        Self {
+            controller: GameController::new(Game::new(grid), SystemClock)
+                .with_interval(Duration::from_millis(100)),
+            scroll_offset: egui::Vec2::ZERO,
+            zoom: 1.0,
+            pattern_file: PatternFileWindow::default(),
+            identify: IdentifyWindow::default(),
+            image_file: ImageFileWindow::default(),
+            gif_window: GifExportWindow::default(),
+            gif_export: None,
+            run: RunWindow::default(),
+            run_reported: Instant::now(),
+            period_search: None,
+            bookmarks: BookmarksPanel::default(),
+            session_file: SessionFileWindow::default(),
+            library: LibraryWindow::default(),
+            randomize: RandomizeWindow::default(),
+            resize: ResizeWindow::default(),
+            settings: SettingsWindow::default(),
+        }
+    }
+
+    fn from_session(session: &Session) -> Result<Self, SessionError> {
+        let mut state = GameState::new(Grid::new(1, 1));
+        state.controller =
+            GameController::new(session.to_game((MAX_WIDTH, MAX_HEIGHT))?, SystemClock)
+                .with_interval(session.interval());
+        state.zoom = session.zoom((MIN_ZOOM, MAX_ZOOM));
+        state.scroll_offset = session.scroll_offset.into();
+        Ok(state)
+    }
+
+    fn to_session(&self) -> Session {
+        Session::capture(
+            &self.controller.game,
+            self.controller.interval(),
+            self.zoom,
+            self.scroll_offset.into(),
+        )
+    }
 }
 
 impl Default for MyApp {
@@ -49,6 +156,7 @@ impl Default for MyApp {
             screen: AppScreen::Setup(SetupState {
                 width: "400".into(),
                 height: "200".into(),
+                session_path: String::new(),
             }),
             toasts: egui_notify::Toasts::default(),
         }
@@ -62,6 +170,8 @@ impl eframe::App for MyApp {
             AppScreen::Playing(_) => self.render_game_ui(ui, ctx),
         });
 
+        self.handle_dropped_files(ctx);
+
         self.toasts.show(ctx);
     }
 }
@@ -102,25 +212,121 @@ impl MyApp {
                 }
             };
 
-            if height > 300 {
-                self.toasts.warning("Max height is 300!");
+            if height > MAX_HEIGHT {
+                self.toasts.warning(format!("Max height is {MAX_HEIGHT}!"));
                 return;
             }
 
-            if width > 500 {
-                self.toasts.warning("Max width is 500!");
+            if width > MAX_WIDTH {
+                self.toasts.warning(format!("Max width is {MAX_WIDTH}!"));
                 return;
             }
 
-            self.screen = AppScreen::Playing(GameState {
-                controller: GameController::new(Game::new(Grid::new(width, height)), SystemClock)
-                    .with_interval(Duration::from_millis(100)),
-                scroll_offset: egui::Vec2::ZERO,
-                zoom: 1.0,
+            self.start_game(ctx, GameState::new(Grid::new(width, height)));
+            return;
+        }
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            ui.label("Session:");
+            ui.text_edit_singleline(&mut setup.session_path);
+        });
+
+        if ui.button("Open session").clicked() {
+            let path = Path::new(setup.session_path.trim()).to_path_buf();
+            match session::load_session(&path).and_then(|s| GameState::from_session(&s)) {
+                Ok(state) => {
+                    self.start_game(ctx, state);
+                    self.toasts.success(format!("Opened {}", path.display()));
+                }
+                Err(e) => {
+                    self.toasts
+                        .error(format!("Could not open {}: {e}", path.display()));
+                }
+            }
+        }
+    }
+
+    fn start_game(&mut self, ctx: &egui::Context, state: GameState) {
+        self.screen = AppScreen::Playing(Box::new(state));
+
+        ctx.set_pixels_per_point(1.0);
+        ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize([1280.0, 720.0].into()));
+    }
+
+    /// Opens pattern files dropped onto the window. On the setup screen the grid
+    /// is sized to fit; in game a drop floats as a paste, or replaces the
+    /// universe when Shift is held.
+    fn handle_dropped_files(&mut self, ctx: &egui::Context) {
+        let hovering = ctx.input(|i| !i.raw.hovered_files.is_empty());
+        if hovering {
+            let painter = ctx.layer_painter(egui::LayerId::new(
+                egui::Order::Foreground,
+                egui::Id::new("drop_hint"),
+            ));
+            let rect = ctx.screen_rect();
+            painter.rect_filled(rect, 0.0, egui::Color32::from_black_alpha(160));
+            painter.text(
+                rect.center(),
+                egui::Align2::CENTER_CENTER,
+                "Drop to paste, Shift+drop to replace",
+                egui::FontId::proportional(20.0),
+                egui::Color32::WHITE,
+            );
+        }
+
+        let (dropped, replace) = ctx.input(|i| (i.raw.dropped_files.clone(), i.modifiers.shift));
+        for file in dropped {
+            match file.path {
+                Some(path) => self.open_dropped_file(ctx, &path, replace),
+                None => {
+                    self.toasts
+                        .error(format!("Could not open {}: no file path", file.name));
+                }
+            }
+        }
+    }
+
+    fn open_dropped_file(&mut self, ctx: &egui::Context, path: &Path, replace: bool) {
+        let AppScreen::Playing(game) = &mut self.screen else {
+            let fitted = formats::read_pattern_file(path, (MAX_WIDTH, MAX_HEIGHT)).map(|p| {
+                let (width, height) = fitting_size(&p);
+                p.to_grid(width, height)
+                    .expect("open_dropped_file: grid sized to fit pattern")
             });
 
-            ctx.set_pixels_per_point(1.0);
-            ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize([1280.0, 720.0].into()));
+            match fitted {
+                Ok(grid) => {
+                    self.start_game(ctx, GameState::new(grid));
+                    self.toasts.success(format!("Opened {}", path.display()));
+                }
+                Err(e) => {
+                    self.toasts
+                        .error(format!("Could not open {}: {e}", path.display()));
+                }
+            }
+            return;
+        };
+
+        let grid = &game.controller.game.grid;
+        let size = (grid.width(), grid.height());
+        match formats::read_pattern_file(path, size) {
+            Ok(pattern) if replace => {
+                let grid = pattern
+                    .to_grid(size.0, size.1)
+                    .expect("open_dropped_file: pattern checked to fit");
+                game.controller.replace_grid(grid);
+                self.toasts.success(format!("Opened {}", path.display()));
+            }
+            Ok(pattern) => {
+                game.controller.start_paste(pattern);
+                self.toasts
+                    .info(format!("Click to place {}", path.display()));
+            }
+            Err(e) => {
+                self.toasts
+                    .error(format!("Could not open {}: {e}", path.display()));
+            }
         }
     }
 
@@ -134,38 +340,59 @@ impl MyApp {
         }
 
         if game.controller.should_tick() {
-            game.controller.tick();
+            game.controller.advance();
+        }
+
+        if game.controller.run_goal().is_some() {
+            ctx.request_repaint();
+            drive_run(game, &mut self.toasts);
+        }
+
+        if game.period_search.is_some() {
+            ctx.request_repaint();
+            drive_period_search(game, &mut self.toasts);
         }
 
         let (scroll, ctrl) = ctx.input(|i| (i.raw_scroll_delta.y, i.modifiers.ctrl));
         if scroll != 0.0 && ctrl {
             let zoom_speed = 0.01;
             let old_zoom = game.zoom;
-            let new_zoom = (game.zoom + scroll * zoom_speed).clamp(0.5, 4.0);
-
-            if new_zoom != old_zoom {
-                if let Some(mouse_pos) = ctx.input(|i| i.pointer.hover_pos()) {
-                    let available_rect = ui.available_rect_before_wrap();
-
-                    let button_bar_height = 30.0;
-                    let scroll_area_rect = egui::Rect::from_min_size(
-                        available_rect.min + egui::Vec2::new(0.0, button_bar_height),
-                        available_rect.size() - egui::Vec2::new(0.0, button_bar_height),
-                    );
-
-                    if scroll_area_rect.contains(mouse_pos) {
-                        let mouse_in_scroll = mouse_pos - scroll_area_rect.min;
-                        let content_point = mouse_in_scroll + game.scroll_offset;
-                        let zoom_ratio = new_zoom / old_zoom;
-                        let new_content_point = content_point * zoom_ratio;
-
-                        game.scroll_offset = new_content_point - mouse_in_scroll;
-                        game.zoom = new_zoom;
-                    }
+            let new_zoom = (game.zoom + scroll * zoom_speed).clamp(MIN_ZOOM, MAX_ZOOM);
+
+            if new_zoom != old_zoom
+                && let Some(mouse_pos) = ctx.input(|i| i.pointer.hover_pos())
+            {
+                let available_rect = ui.available_rect_before_wrap();
+
+                // Toolbar, tool palette and timeline rows.
+                let button_bar_height = 92.0;
+                let scroll_area_rect = egui::Rect::from_min_size(
+                    available_rect.min + egui::Vec2::new(0.0, button_bar_height),
+                    available_rect.size() - egui::Vec2::new(0.0, button_bar_height),
+                );
+
+                if scroll_area_rect.contains(mouse_pos) {
+                    let mouse_in_scroll = mouse_pos - scroll_area_rect.min;
+                    let content_point = mouse_in_scroll + game.scroll_offset;
+                    let zoom_ratio = new_zoom / old_zoom;
+                    let new_content_point = content_point * zoom_ratio;
+
+                    game.scroll_offset = new_content_point - mouse_in_scroll;
+                    game.zoom = new_zoom;
                 }
             }
         }
 
+        handle_shortcuts(ctx, game, &mut self.toasts);
+
+        let generation = game.controller.game.generation();
+        if let Some(action) =
+            game.bookmarks
+                .show_inside(ui, game.controller.bookmarks(), generation)
+        {
+            apply_bookmark_action(game, action, &mut self.toasts);
+        }
+
         ui.horizontal(|ui| {
             if game.controller.is_playing() {
                 if ui.button("⏸").clicked() {
@@ -175,21 +402,385 @@ impl MyApp {
                 game.controller.play();
             }
 
-            if ui.button("⏭").clicked() {
-                game.controller.game.tick();
+            if ui
+                .add_enabled(
+                    game.controller.can_step_back() && !game.controller.is_reversing(),
+                    egui::Button::new("⏪"),
+                )
+                .on_hover_text("Play backwards")
+                .clicked()
+            {
+                game.controller.play_reverse();
+            }
+
+            if ui
+                .add_enabled(game.controller.can_step_back(), egui::Button::new("⏮"))
+                .on_hover_text("Step back")
+                .clicked()
+            {
+                game.controller.pause();
+                game.controller.step_back();
+            }
+
+            if ui.button("⏭").on_hover_text("Step").clicked() {
+                game.controller.tick();
+            }
+
+            if ui
+                .add_enabled(game.controller.can_reset(), egui::Button::new("⟲ Reset"))
+                .on_hover_text("Back to the grid as it was when first played")
+                .clicked()
+            {
+                game.controller.reset();
+            }
+
+            ui.label(format!("gen: {}", game.controller.game.generation()));
+
+            if game.controller.run_goal().is_some() {
+                ui.spinner();
+                if ui.button("✖").on_hover_text("Cancel run").clicked() {
+                    game.controller.cancel_run();
+                    self.toasts.info(format!(
+                        "Run cancelled at gen {}",
+                        game.controller.game.generation()
+                    ));
+                }
+            } else if ui.button("⏩").on_hover_text("Run…").clicked() {
+                game.run.open();
+            }
+
+            if ui.button("🔖").on_hover_text("Bookmarks").clicked() {
+                game.bookmarks.toggle();
+            }
+
+            if ui
+                .add_enabled(game.controller.can_undo(), egui::Button::new("↶"))
+                .on_hover_text("Undo (Ctrl+Z)")
+                .clicked()
+            {
+                game.controller.undo();
+            }
+
+            if ui
+                .add_enabled(game.controller.can_redo(), egui::Button::new("↷"))
+                .on_hover_text("Redo (Ctrl+Shift+Z)")
+                .clicked()
+            {
+                game.controller.redo();
+            }
+
+            if ui.button("🗑").on_hover_text("Clear").clicked() {
+                game.controller.clear();
+            }
+
+            if ui.button("📐").on_hover_text("Resize grid").clicked() {
+                let grid = &game.controller.game.grid;
+                game.resize.open(grid.width(), grid.height());
+            }
+
+            if ui.button("🎲").on_hover_text("Randomize").clicked() {
+                game.randomize.open();
+            }
+
+            if ui.button("📚").on_hover_text("Pattern library").clicked() {
+                game.library.open();
+            }
+
+            if ui.button("📂").on_hover_text("Pattern file").clicked() {
+                game.pattern_file.open();
+            }
+
+            if ui.button("🖼").on_hover_text("Image").clicked() {
+                game.image_file.open();
+            }
+
+            if ui.button("💾").on_hover_text("Session").clicked() {
+                game.session_file.open();
+            }
+
+            if ui.button("🎞").on_hover_text("Export GIF").clicked() {
+                game.gif_window.open();
+            }
+
+            if ui.button("⚙").on_hover_text("Settings").clicked() {
+                game.settings.open();
+            }
+
+            if ui.button("🔎").on_hover_text("Identify object").clicked() {
+                let pattern = game
+                    .controller
+                    .selection_pattern()
+                    .unwrap_or_else(|| Pattern::from_grid(&game.controller.game.grid));
+                game.identify.show_result(apgcode::encode(&pattern));
+            }
+
+            if let Some((search, _)) = &game.period_search {
+                let search_generation = search.generation();
+                ui.spinner();
+                if ui
+                    .button("✖")
+                    .on_hover_text(format!("Cancel period search at gen {}", search_generation))
+                    .clicked()
+                {
+                    game.period_search = None;
+                }
+            } else if ui.button("🔁").on_hover_text("Find period").clicked() {
+                let selection = game.controller.selection();
+                let target = if selection.is_some() {
+                    "Selection"
+                } else {
+                    "Grid"
+                };
+                let search = game
+                    .controller
+                    .game
+                    .period_search(selection, PERIOD_SEARCH_LIMIT);
+                game.period_search = Some((search, target));
+            }
+
+            if game.controller.selection().is_some() {
+                ui.separator();
+                ui.label("Selection:");
+                if ui.button("Copy").clicked() {
+                    copy_pattern(ctx, game.controller.selection_pattern(), &mut self.toasts);
+                }
+                if ui.button("Cut").clicked() {
+                    copy_pattern(ctx, game.controller.cut_selection(), &mut self.toasts);
+                }
+                if ui.button("Clear").clicked() {
+                    game.controller.clear_selection();
+                }
+                if ui.button("Fill").clicked() {
+                    game.controller.fill_selection();
+                }
+                if ui.button("Invert").clicked() {
+                    game.controller.invert_selection();
+                }
+                let transforms = [
+                    ("⟲", "Rotate 90° anticlockwise", Transform::Rotate270),
+                    ("⟳", "Rotate 90° clockwise (R)", Transform::Rotate90),
+                    ("180°", "Rotate 180°", Transform::Rotate180),
+                    ("↔", "Flip horizontally (H)", Transform::FlipHorizontal),
+                    ("↕", "Flip vertically (V)", Transform::FlipVertical),
+                    ("⤡", "Transpose (T)", Transform::Transpose),
+                ];
+                for (label, hint, transform) in transforms {
+                    if ui.button(label).on_hover_text(hint).clicked() {
+                        game.controller.transform_selection(transform);
+                    }
+                }
+                if ui.button("Crop").on_hover_text("Crop universe").clicked() {
+                    game.controller.crop_to_selection();
+                }
+                if ui.button("✖").on_hover_text("Select none (Esc)").clicked() {
+                    game.controller.select_none();
+                }
+            }
+
+            if game.controller.paste_preview().is_some() {
+                ui.separator();
+                let label = if game.controller.is_stamping() {
+                    "Stamp"
+                } else {
+                    "Paste"
+                };
+                egui::ComboBox::from_label(label)
+                    .selected_text(game.controller.paste_mode.name())
+                    .show_ui(ui, |ui| {
+                        for mode in PasteMode::ALL {
+                            ui.selectable_value(&mut game.controller.paste_mode, mode, mode.name());
+                        }
+                    });
+                if ui.button("⟳").on_hover_text("Rotate (R)").clicked() {
+                    game.controller.transform_paste(Transform::Rotate90);
+                }
+                if ui
+                    .button("↔")
+                    .on_hover_text("Flip horizontally (H)")
+                    .clicked()
+                {
+                    game.controller.transform_paste(Transform::FlipHorizontal);
+                }
+                if ui
+                    .button("↕")
+                    .on_hover_text("Flip vertically (V)")
+                    .clicked()
+                {
+                    game.controller.transform_paste(Transform::FlipVertical);
+                }
+                if ui.button("✖").on_hover_text("Cancel paste (Esc)").clicked() {
+                    game.controller.cancel_paste();
+                }
+            }
+        });
+
+        ui.horizontal(|ui| {
+            let controller = &mut game.controller;
+            for tool in Tool::ALL {
+                ui.selectable_value(&mut controller.tool, tool, tool.name());
+            }
+
+            ui.separator();
+            ui.label("Brush:");
+            ui.add(egui::DragValue::new(&mut controller.brush_size).range(1..=32));
+
+            ui.separator();
+            ui.label("Paint:");
+            ui.selectable_value(&mut controller.draw_alive, true, "Alive");
+            ui.selectable_value(&mut controller.draw_alive, false, "Dead");
+
+            if matches!(controller.tool, Tool::Rectangle | Tool::Ellipse) {
+                ui.checkbox(&mut controller.fill_shapes, "Filled");
+            }
+
+            ui.separator();
+            egui::ComboBox::from_label("Symmetry")
+                .selected_text(controller.symmetry.name())
+                .show_ui(ui, |ui| {
+                    for symmetry in DrawSymmetry::ALL {
+                        ui.selectable_value(&mut controller.symmetry, symmetry, symmetry.name());
+                    }
+                });
+            if controller.symmetry != DrawSymmetry::None {
+                let grid = &controller.game.grid;
+                let limits = (grid.width() as f64 - 1.0, grid.height() as f64 - 1.0);
+                let (cx, cy) = &mut controller.symmetry_centre;
+                ui.label("Centre:");
+                half_cell_drag_value(ui, cx, limits.0);
+                half_cell_drag_value(ui, cy, limits.1);
+                if ui.button("⌖").on_hover_text("Centre of grid").clicked() {
+                    controller.centre_symmetry();
+                }
             }
         });
 
+        let current = game.controller.game.generation();
+        if let Some(generation) = TimelineView::new(game.controller.generations(), current).show(ui)
+        {
+            game.controller.pause();
+            game.controller.seek(generation);
+        }
+
+        if let Some(settled) = game.controller.take_settled() {
+            self.toasts.info(format!("Paused: {settled}"));
+        }
+
+        if let Some(action) = game.pattern_file.show(ctx) {
+            apply_pattern_file_action(game, action, &mut self.toasts);
+        }
+
+        if let Some(action) = game.image_file.show(ctx) {
+            apply_image_file_action(game, action, &mut self.toasts);
+        }
+
+        let controller = &mut game.controller;
+        game.settings
+            .show(ctx, &mut controller.bindings, &mut controller.auto_pause);
+
+        if let Some(action) = game.library.show(ctx) {
+            apply_library_action(game, action, &mut self.toasts);
+        }
+
+        if let Some(action) = game.run.show(ctx) {
+            apply_run_action(game, action, &mut self.toasts);
+        }
+
+        if let Some(action) = game.resize.show(ctx, MAX_WIDTH, MAX_HEIGHT) {
+            apply_resize_action(game, action, &mut self.toasts);
+        }
+
+        let target = if game.controller.selection().is_some() {
+            "selection"
+        } else {
+            "grid"
+        };
+        if let Some(RandomizeAction::Apply(options)) = game.randomize.show(ctx, target) {
+            game.controller.randomize(&options);
+            self.toasts.info(format!(
+                "Filled {target} with {} soup, seed {}",
+                options.symmetry.name(),
+                options.seed
+            ));
+        }
+
+        if let Some(action) = game.session_file.show(ctx) {
+            apply_session_file_action(game, action, &mut self.toasts);
+        }
+
+        let gif_progress = game.gif_export.as_ref().map(GifExport::progress);
+        if let Some(GifExportAction::Export(path, options)) =
+            game.gif_window.show(ctx, gif_progress)
+        {
+            let copy = game.controller.game.clone();
+            match GifExport::start(copy, path.clone(), options) {
+                Ok(export) => game.gif_export = Some(export),
+                Err(e) => {
+                    self.toasts
+                        .error(format!("Could not export {}: {e}", path.display()));
+                }
+            }
+        }
+
+        if let Some(export) = &mut game.gif_export {
+            ctx.request_repaint();
+            if let Some(result) = export.try_finish() {
+                match result {
+                    Ok(()) => {
+                        self.toasts
+                            .success(format!("Exported {}", export.path.display()));
+                    }
+                    Err(e) => {
+                        self.toasts
+                            .error(format!("Could not export {}: {e}", export.path.display()));
+                    }
+                }
+                game.gif_export = None;
+            }
+        }
+
+        if let Some(IdentifyAction::Load(code)) = game.identify.show(ctx) {
+            let grid = &game.controller.game.grid;
+            let loaded = apgcode::decode(&code)
+                .map_err(|e| e.to_string())
+                .and_then(|p| {
+                    p.to_grid(grid.width(), grid.height())
+                        .map_err(|e| e.to_string())
+                });
+
+            match loaded {
+                Ok(grid) => game.controller.replace_grid(grid),
+                Err(e) => {
+                    self.toasts.error(format!("Could not load {code}: {e}"));
+                }
+            }
+        }
+
         egui::ScrollArea::both()
             .scroll_offset(game.scroll_offset)
             .show(ui, |ui| {
                 let cell_size = 14.0 * game.zoom;
-                let result = GridView::new(&game.controller.game.grid, cell_size).show(ui);
+                let preview = game.controller.preview();
+                let result = GridView::new(&game.controller.game.grid, cell_size)
+                    .with_preview(preview.as_ref())
+                    .with_selection(game.controller.selection())
+                    .with_symmetry(game.controller.symmetry, game.controller.symmetry_centre)
+                    .show(ui);
+
+                let (pressed, released) = ui
+                    .ctx()
+                    .input(|i| (i.pointer.any_pressed(), i.pointer.any_released()));
+                if pressed && result.response.hovered() {
+                    game.controller.begin_stroke();
+                }
 
                 if let Some(event) = result.pointer_event {
                     game.controller.handle_pointer_event(event);
                 }
 
+                if released {
+                    game.controller.end_stroke();
+                }
+
                 if ui.ctx().input(|i| i.pointer.middle_down()) {
                     let delta = ui.ctx().input(|i| i.pointer.delta());
                     game.scroll_offset -= delta;
@@ -197,3 +788,394 @@ impl MyApp {
             });
     }
 }
+
+/// Grid size holding the pattern with a margin, within the maximum grid size.
+fn fitting_size(pattern: &Pattern) -> (usize, usize) {
+    let (width, height) = pattern.bounds().map_or((0, 0), |b| (b.width(), b.height()));
+    (
+        (width + 2 * DROP_MARGIN).min(MAX_WIDTH),
+        (height + 2 * DROP_MARGIN).min(MAX_HEIGHT),
+    )
+}
+
+/// Ctrl+V floats clipboard text as a paste, Ctrl+C copies the selection (or
+/// the grid) as RLE, Ctrl+X cuts and Delete clears the selection, Ctrl+Z and
+/// Ctrl+Shift+Z undo and redo, and R/H/V/T/Esc transform or drop a floating
+/// paste, or else the selection. Ignored while a text field has focus.
+fn handle_shortcuts(ctx: &egui::Context, game: &mut GameState, toasts: &mut egui_notify::Toasts) {
+    if ctx.memory(|m| m.focused().is_some()) {
+        return;
+    }
+
+    let (pasted, copied, cut) = ctx.input(|i| {
+        let pasted = i.events.iter().find_map(|e| match e {
+            egui::Event::Paste(text) => Some(text.clone()),
+            _ => None,
+        });
+        let copied = i.events.iter().any(|e| matches!(e, egui::Event::Copy));
+        let cut = i.events.iter().any(|e| matches!(e, egui::Event::Cut));
+        (pasted, copied, cut)
+    });
+
+    if let Some(text) = pasted {
+        let grid = &game.controller.game.grid;
+        match formats::parse_text(&text, (grid.width(), grid.height())) {
+            Ok(pattern) if !pattern.is_empty() => game.controller.start_paste(pattern),
+            Ok(_) => {
+                toasts.warning("Pasted pattern is empty");
+            }
+            Err(e) => {
+                toasts.error(format!("Could not paste: {e}"));
+            }
+        }
+    }
+
+    if copied {
+        let pattern = game
+            .controller
+            .selection_pattern()
+            .unwrap_or_else(|| Pattern::from_grid(&game.controller.game.grid));
+        copy_pattern(ctx, Some(pattern), toasts);
+    }
+
+    if cut {
+        copy_pattern(ctx, game.controller.cut_selection(), toasts);
+    }
+
+    if ctx.input(|i| i.key_pressed(egui::Key::Delete)) {
+        game.controller.clear_selection();
+    }
+
+    let redo = egui::Modifiers::COMMAND | egui::Modifiers::SHIFT;
+    if ctx.input_mut(|i| i.consume_key(redo, egui::Key::Z)) {
+        game.controller.redo();
+    } else if ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::Z)) {
+        game.controller.undo();
+    }
+
+    let pasting = game.controller.paste_preview().is_some();
+    let keys = [
+        (egui::Key::R, Transform::Rotate90),
+        (egui::Key::H, Transform::FlipHorizontal),
+        (egui::Key::V, Transform::FlipVertical),
+        (egui::Key::T, Transform::Transpose),
+    ];
+    for (key, transform) in keys {
+        if ctx.input(|i| i.key_pressed(key) && !i.modifiers.command) {
+            if pasting {
+                game.controller.transform_paste(transform);
+            } else {
+                game.controller.transform_selection(transform);
+            }
+        }
+    }
+
+    if ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        if pasting {
+            game.controller.cancel_paste();
+        } else {
+            game.controller.select_none();
+        }
+    }
+}
+
+fn copy_pattern(ctx: &egui::Context, pattern: Option<Pattern>, toasts: &mut egui_notify::Toasts) {
+    if let Some(pattern) = pattern {
+        ctx.copy_text(formats::rle::write(&pattern));
+        toasts.info(format!("Copied {} cells as RLE", pattern.population()));
+    }
+}
+
+fn apply_session_file_action(
+    game: &mut GameState,
+    action: SessionFileAction,
+    toasts: &mut egui_notify::Toasts,
+) {
+    match action {
+        SessionFileAction::Open(path) => {
+            match session::load_session(&path).and_then(|s| GameState::from_session(&s)) {
+                Ok(state) => {
+                    game.controller = state.controller;
+                    game.zoom = state.zoom;
+                    game.scroll_offset = state.scroll_offset;
+                    toasts.success(format!("Opened {}", path.display()));
+                }
+                Err(e) => {
+                    toasts.error(format!("Could not open {}: {e}", path.display()));
+                }
+            }
+        }
+        SessionFileAction::Save(path) => match session::save_session(&path, &game.to_session()) {
+            Ok(()) => {
+                toasts.success(format!("Saved {}", path.display()));
+            }
+            Err(e) => {
+                toasts.error(format!("Could not save {}: {e}", path.display()));
+            }
+        },
+    }
+}
+
+fn apply_pattern_file_action(
+    game: &mut GameState,
+    action: PatternFileAction,
+    toasts: &mut egui_notify::Toasts,
+) {
+    match action {
+        PatternFileAction::Open(path) => {
+            let grid = &game.controller.game.grid;
+            let size = (grid.width(), grid.height());
+            let loaded = formats::read_pattern_file(&path, size)
+                .and_then(|p| Ok(p.to_grid(size.0, size.1)?));
+
+            match loaded {
+                Ok(grid) => {
+                    game.controller.replace_grid(grid);
+                    toasts.success(format!("Opened {}", path.display()));
+                }
+                Err(e) => {
+                    toasts.error(format!("Could not open {}: {e}", path.display()));
+                }
+            }
+        }
+        PatternFileAction::Save(path, format) => {
+            let pattern = Pattern::from_grid(&game.controller.game.grid);
+            match formats::write_pattern_file(&path, format, &pattern) {
+                Ok(()) => {
+                    toasts.success(format!("Saved {} as {}", path.display(), format.name()));
+                }
+                Err(e) => {
+                    toasts.error(format!("Could not save {}: {e}", path.display()));
+                }
+            }
+        }
+    }
+}
+
+/// Edits a coordinate stored in half cells, shown in cells.
+fn half_cell_drag_value(ui: &mut egui::Ui, half_cells: &mut i64, max: f64) {
+    let mut cells = *half_cells as f64 / 2.0;
+    let response = ui.add(
+        egui::DragValue::new(&mut cells)
+            .speed(0.5)
+            .range(0.0..=max)
+            .fixed_decimals(1),
+    );
+    if response.changed() {
+        *half_cells = (cells * 2.0).round() as i64;
+    }
+}
+
+/// Generations evolved between checks of the frame's time budget.
+const RUN_BATCH: u32 = 16;
+
+/// Advances the fast run for a slice of the frame, toasting its progress
+/// about once a second and its outcome when it ends.
+fn drive_run(game: &mut GameState, toasts: &mut egui_notify::Toasts) {
+    let deadline = Instant::now() + Duration::from_millis(30);
+    let outcome = loop {
+        if let Some(outcome) = game.controller.continue_run(RUN_BATCH) {
+            break Some(outcome);
+        }
+        if game.controller.run_goal().is_none() || Instant::now() >= deadline {
+            break None;
+        }
+    };
+
+    let generation = game.controller.game.generation();
+    match outcome {
+        Some(RunOutcome::Reached(g)) => toasts.success(format!("Reached gen {g}")),
+        Some(RunOutcome::Changed(g)) => toasts.success(format!("Changed at gen {g}")),
+        Some(RunOutcome::Extinct(g)) => toasts.success(format!("Extinct at gen {g}")),
+        Some(RunOutcome::Stable(g)) => toasts.success(format!("Stable at gen {g}")),
+        None if game.run_reported.elapsed() >= Duration::from_secs(1) => {
+            game.run_reported = Instant::now();
+            let message = match game.controller.run_goal() {
+                Some(RunGoal::Generation(target)) => {
+                    format!("Running… gen {generation} of {target}")
+                }
+                _ => format!("Running… gen {generation}"),
+            };
+            toasts.info(message).duration(Some(Duration::from_secs(1)))
+        }
+        None => return,
+    };
+}
+
+/// Advances the period search for a slice of the frame, toasting what it
+/// found once it ends.
+fn drive_period_search(game: &mut GameState, toasts: &mut egui_notify::Toasts) {
+    let Some((search, target)) = &mut game.period_search else {
+        return;
+    };
+    let deadline = Instant::now() + Duration::from_millis(30);
+    let outcome = loop {
+        if let Some(outcome) = search.continue_search(RUN_BATCH) {
+            break outcome;
+        }
+        if Instant::now() >= deadline {
+            return;
+        }
+    };
+
+    match outcome {
+        SearchOutcome::Found(cycle) => {
+            toasts.success(format!("{target}: {cycle}"));
+        }
+        SearchOutcome::NotFound => {
+            toasts.warning(format!(
+                "{target}: no repeat within {PERIOD_SEARCH_LIMIT} generations"
+            ));
+        }
+    }
+    game.period_search = None;
+}
+
+fn apply_run_action(game: &mut GameState, action: RunAction, toasts: &mut egui_notify::Toasts) {
+    let current = game.controller.game.generation();
+    match action {
+        RunAction::Advance(steps) => {
+            game.controller
+                .start_run(RunGoal::Generation(current.saturating_add(steps)));
+        }
+        RunAction::GoTo(target) => {
+            if !game.controller.go_to_generation(target) {
+                toasts.error(format!("Gen {target} is no longer in the history"));
+                return;
+            }
+        }
+        RunAction::Until(goal) => game.controller.start_run(goal),
+    }
+    game.run_reported = Instant::now();
+}
+
+fn apply_bookmark_action(
+    game: &mut GameState,
+    action: BookmarkAction,
+    toasts: &mut egui_notify::Toasts,
+) {
+    match action {
+        BookmarkAction::Add(name) => {
+            toasts.info(format!(
+                "Bookmarked {name} at gen {}",
+                game.controller.game.generation()
+            ));
+            game.controller.add_bookmark(name);
+        }
+        BookmarkAction::Jump(index) => game.controller.jump_to_bookmark(index),
+        BookmarkAction::Remove(index) => game.controller.remove_bookmark(index),
+    }
+}
+
+fn apply_library_action(
+    game: &mut GameState,
+    action: LibraryAction,
+    toasts: &mut egui_notify::Toasts,
+) {
+    match action {
+        LibraryAction::Stamp(name, pattern) => {
+            game.controller.start_stamp(pattern);
+            toasts.info(format!(
+                "Click to stamp {name}; R/H/V/T to turn, Esc to stop"
+            ));
+        }
+        LibraryAction::LoadFolder(dir) => {
+            match game.library.load_folder(&dir, (MAX_WIDTH, MAX_HEIGHT)) {
+                Ok(loaded) if loaded.skipped.is_empty() => {
+                    toasts.success(format!(
+                        "Loaded {} patterns from {}",
+                        loaded.entries.len(),
+                        dir.display()
+                    ));
+                }
+                Ok(loaded) => {
+                    toasts.warning(format!("{}: {loaded}", dir.display()));
+                }
+                Err(e) => {
+                    toasts.error(format!("Could not load library folder: {e}"));
+                }
+            }
+        }
+    }
+}
+
+fn apply_resize_action(
+    game: &mut GameState,
+    action: ResizeAction,
+    toasts: &mut egui_notify::Toasts,
+) {
+    match action {
+        ResizeAction::Resize {
+            width,
+            height,
+            anchor,
+        } => {
+            game.controller.resize_grid(width, height, anchor);
+            toasts.info(format!("Resized grid to {width}×{height}"));
+        }
+        ResizeAction::Translate(dx, dy) => game.controller.translate_grid(dx, dy),
+        ResizeAction::CropToLive { margin } => {
+            let grid = &game.controller.game.grid;
+            let Some(bounds) = grid.live_bounds() else {
+                toasts.warning("Nothing alive to crop to");
+                return;
+            };
+            let (width, height) = (bounds.width + 2 * margin, bounds.height + 2 * margin);
+            if width > MAX_WIDTH || height > MAX_HEIGHT {
+                toasts.error(format!(
+                    "Cropped grid would be {width}×{height}, over the {MAX_WIDTH}×{MAX_HEIGHT} limit"
+                ));
+                return;
+            }
+            game.controller.crop_to_live(margin);
+            toasts.info(format!("Cropped grid to {width}×{height}"));
+        }
+    }
+}
+
+fn apply_image_file_action(
+    game: &mut GameState,
+    action: ImageFileAction,
+    toasts: &mut egui_notify::Toasts,
+) {
+    match action {
+        ImageFileAction::ExportPng(path, style) => {
+            match bitmap::export_png(&path, &game.controller.export_grid(), &style) {
+                Ok(()) => {
+                    toasts.success(format!("Exported {}", path.display()));
+                }
+                Err(e) => {
+                    toasts.error(format!("Could not export {}: {e}", path.display()));
+                }
+            }
+        }
+        ImageFileAction::ExportSvg(path, options) => {
+            let controller = &game.controller;
+            let caption = controller.game.caption();
+            match svg::export_svg(&path, &controller.export_grid(), &options, &caption) {
+                Ok(()) => {
+                    toasts.success(format!("Exported {}", path.display()));
+                }
+                Err(e) => {
+                    toasts.error(format!("Could not export {}: {e}", path.display()));
+                }
+            }
+        }
+        ImageFileAction::Import(path, options) => {
+            let grid = &game.controller.game.grid;
+            let size = (grid.width(), grid.height());
+            let loaded = bitmap::import_image(&path, &options, size)
+                .and_then(|p| Ok(p.to_grid(size.0, size.1)?));
+
+            match loaded {
+                Ok(grid) => {
+                    game.controller.replace_grid(grid);
+                    toasts.success(format!("Imported {}", path.display()));
+                }
+                Err(e) => {
+                    toasts.error(format!("Could not import {}: {e}", path.display()));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,448 @@
+use std::fmt::Display;
+
+use crate::{grid::Grid, grid_evolver::GridEvolver};
+
+/// Live cells of a pattern in its own coordinate space, which may be negative.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Pattern {
+    cells: Vec<(i64, i64)>,
+}
+
+/// One of the eight symmetries of the square.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Transform {
+    Identity,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    FlipHorizontal,
+    FlipVertical,
+    Transpose,
+    AntiTranspose,
+}
+
+impl Transform {
+    pub const ALL: [Transform; 8] = [
+        Transform::Identity,
+        Transform::Rotate90,
+        Transform::Rotate180,
+        Transform::Rotate270,
+        Transform::FlipHorizontal,
+        Transform::FlipVertical,
+        Transform::Transpose,
+        Transform::AntiTranspose,
+    ];
+
+    /// Maps a cell, rotating clockwise with y pointing down.
+    pub fn apply(self, (x, y): (i64, i64)) -> (i64, i64) {
+        match self {
+            Transform::Identity => (x, y),
+            Transform::Rotate90 => (-y, x),
+            Transform::Rotate180 => (-x, -y),
+            Transform::Rotate270 => (y, -x),
+            Transform::FlipHorizontal => (-x, y),
+            Transform::FlipVertical => (x, -y),
+            Transform::Transpose => (y, x),
+            Transform::AntiTranspose => (-y, -x),
+        }
+    }
+
+    /// True for the transforms that swap a shape's width and height.
+    pub fn swaps_axes(self) -> bool {
+        matches!(
+            self,
+            Transform::Rotate90
+                | Transform::Rotate270
+                | Transform::Transpose
+                | Transform::AntiTranspose
+        )
+    }
+}
+
+/// How pasted cells combine with the cells already on the grid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum PasteMode {
+    #[default]
+    Or,
+    Xor,
+    /// Clears the pattern's bounding box before setting its cells.
+    Overwrite,
+}
+
+impl PasteMode {
+    pub const ALL: [PasteMode; 3] = [PasteMode::Or, PasteMode::Xor, PasteMode::Overwrite];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            PasteMode::Or => "OR",
+            PasteMode::Xor => "XOR",
+            PasteMode::Overwrite => "Overwrite",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Bounds {
+    pub min_x: i64,
+    pub min_y: i64,
+    pub max_x: i64,
+    pub max_y: i64,
+}
+
+impl Bounds {
+    /// Saturates rather than overflowing for bounds spanning most of `i64`.
+    pub fn width(&self) -> usize {
+        span(self.min_x, self.max_x)
+    }
+
+    pub fn height(&self) -> usize {
+        span(self.min_y, self.max_y)
+    }
+
+    pub fn check_fits(&self, grid_width: usize, grid_height: usize) -> Result<(), PatternError> {
+        if self.width() > grid_width || self.height() > grid_height {
+            return Err(PatternError::TooLarge {
+                width: self.width(),
+                height: self.height(),
+                grid_width,
+                grid_height,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl Pattern {
+    pub fn new(mut cells: Vec<(i64, i64)>) -> Self {
+        cells.sort_unstable_by_key(|&(x, y)| (y, x));
+        cells.dedup();
+        Pattern { cells }
+    }
+
+    pub fn from_grid(grid: &Grid) -> Self {
+        Pattern::new(
+            grid.live_cells()
+                .map(|(x, y)| (x as i64, y as i64))
+                .collect(),
+        )
+    }
+
+    /// Live cells sorted row by row.
+    pub fn cells(&self) -> &[(i64, i64)] {
+        &self.cells
+    }
+
+    pub fn bounds(&self) -> Option<Bounds> {
+        let (&(x, y), rest) = self.cells.split_first()?;
+        let init = Bounds {
+            min_x: x,
+            min_y: y,
+            max_x: x,
+            max_y: y,
+        };
+
+        Some(rest.iter().fold(init, |b, &(x, y)| Bounds {
+            min_x: b.min_x.min(x),
+            min_y: b.min_y.min(y),
+            max_x: b.max_x.max(x),
+            max_y: b.max_y.max(y),
+        }))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn population(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn translated(&self, dx: i64, dy: i64) -> Self {
+        Pattern::new(self.cells.iter().map(|&(x, y)| (x + dx, y + dy)).collect())
+    }
+
+    /// Applies the transform, keeping the bounding box's top-left in place.
+    pub fn transformed(&self, transform: Transform) -> Self {
+        let Some(b) = self.bounds() else {
+            return Pattern::default();
+        };
+        let moved = Pattern::new(self.cells.iter().map(|&c| transform.apply(c)).collect());
+        moved.normalized().translated(b.min_x, b.min_y)
+    }
+
+    /// Moves the pattern so its bounding box starts at (0, 0).
+    pub fn normalized(&self) -> Self {
+        match self.bounds() {
+            Some(b) => self.translated(-b.min_x, -b.min_y),
+            None => Pattern::default(),
+        }
+    }
+
+    /// Evolves the pattern one generation on an unbounded plane.
+    pub fn step(&self) -> Self {
+        let Some(b) = self.bounds() else {
+            return Pattern::default();
+        };
+
+        // A one-cell margin is enough room for every possible birth.
+        let mut grid = Grid::new(b.width() + 2, b.height() + 2);
+        for &(x, y) in &self.cells {
+            let coord = ((x - b.min_x + 1) as usize, (y - b.min_y + 1) as usize);
+            grid.set_cell_at_coord(coord, true)
+                .expect("step: cell outside of padded bounds");
+        }
+
+        let (cells, _) =
+            GridEvolver::next_generation(&grid).expect("step: computing next generation failed");
+        grid.set_cells(cells)
+            .expect("step: applying next generation failed");
+
+        Pattern::from_grid(&grid).translated(b.min_x - 1, b.min_y - 1)
+    }
+
+    /// Moves the pattern so its bounding box is centred on (0, 0).
+    pub fn centered(&self) -> Self {
+        match self.bounds() {
+            Some(b) => self.translated(
+                -b.min_x - (b.width() / 2) as i64,
+                -b.min_y - (b.height() / 2) as i64,
+            ),
+            None => Pattern::default(),
+        }
+    }
+
+    pub fn check_fits(&self, width: usize, height: usize) -> Result<(), PatternError> {
+        match self.bounds() {
+            Some(b) => b.check_fits(width, height),
+            None => Ok(()),
+        }
+    }
+
+    /// Builds a grid of the given size with the pattern centred in it.
+    pub fn to_grid(&self, width: usize, height: usize) -> Result<Grid, PatternError> {
+        let mut grid = Grid::new(width, height);
+        let Some(b) = self.bounds() else {
+            return Ok(grid);
+        };
+
+        b.check_fits(width, height)?;
+
+        let dx = (width - b.width()) as i64 / 2 - b.min_x;
+        let dy = (height - b.height()) as i64 / 2 - b.min_y;
+        for &(x, y) in &self.cells {
+            grid.set_cell_at_coord(((x + dx) as usize, (y + dy) as usize), true)
+                .expect("to_grid: cell outside of checked bounds");
+        }
+
+        Ok(grid)
+    }
+
+    /// Draws the pattern onto the grid shifted by `origin`, dropping cells that
+    /// fall outside of it.
+    pub fn paste_onto(&self, grid: &mut Grid, origin: (i64, i64), mode: PasteMode) {
+        let (ox, oy) = origin;
+
+        if mode == PasteMode::Overwrite
+            && let Some(b) = self.bounds()
+        {
+            for y in b.min_y..=b.max_y {
+                for x in b.min_x..=b.max_x {
+                    if let Some(coord) = grid.checked_coord((ox + x, oy + y)) {
+                        let _ = grid.set_cell_at_coord(coord, false);
+                    }
+                }
+            }
+        }
+
+        for &(x, y) in &self.cells {
+            let Some(coord) = grid.checked_coord((ox + x, oy + y)) else {
+                continue;
+            };
+            let _ = match mode {
+                PasteMode::Or | PasteMode::Overwrite => grid.set_cell_at_coord(coord, true),
+                PasteMode::Xor => grid.toggle_cell_at_coord(coord),
+            };
+        }
+    }
+}
+
+fn span(min: i64, max: i64) -> usize {
+    usize::try_from(max.abs_diff(min))
+        .ok()
+        .and_then(|diff| diff.checked_add(1))
+        .unwrap_or(usize::MAX)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PatternError {
+    TooLarge {
+        width: usize,
+        height: usize,
+        grid_width: usize,
+        grid_height: usize,
+    },
+}
+
+impl Display for PatternError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PatternError::TooLarge {
+                width,
+                height,
+                grid_width,
+                grid_height,
+            } => write!(
+                f,
+                "pattern is {width}x{height} but the grid is only {grid_width}x{grid_height}"
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pattern_from_grid() {
+        let mut grid = Grid::new(4, 4);
+        let _ = grid.set_cell_at_coord((2, 1), true);
+        let _ = grid.set_cell_at_coord((0, 3), true);
+
+        let pattern = Pattern::from_grid(&grid);
+
+        assert_eq!(pattern.cells(), &[(2, 1), (0, 3)]);
+    }
+
+    #[test]
+    fn test_pattern_bounds() {
+        let pattern = Pattern::new(vec![(-2, 1), (3, -4), (0, 0)]);
+
+        let bounds = pattern.bounds().unwrap();
+
+        assert_eq!((bounds.min_x, bounds.min_y), (-2, -4));
+        assert_eq!((bounds.max_x, bounds.max_y), (3, 1));
+        assert_eq!((bounds.width(), bounds.height()), (6, 6));
+        assert!(Pattern::default().bounds().is_none());
+    }
+
+    #[test]
+    fn test_extreme_bounds_saturate() {
+        let pattern = Pattern::new(vec![(i64::MIN, 0), (i64::MAX, 0)]);
+
+        let bounds = pattern.bounds().unwrap();
+
+        assert_eq!((bounds.width(), bounds.height()), (usize::MAX, 1));
+    }
+
+    #[test]
+    fn test_centering_pattern() {
+        let pattern = Pattern::new(vec![(7, 7), (9, 8)]);
+
+        assert_eq!(pattern.centered().cells(), &[(-1, -1), (1, 0)]);
+    }
+
+    #[test]
+    fn test_normalizing_negative_coordinates() {
+        let pattern = Pattern::new(vec![(-1, -1), (1, 0)]);
+
+        assert_eq!(pattern.normalized().cells(), &[(0, 0), (2, 1)]);
+    }
+
+    #[test]
+    fn test_transforming_keeps_top_left() {
+        let l_shape = Pattern::new(vec![(5, 5), (5, 6), (5, 7), (6, 7)]);
+
+        assert_eq!(
+            l_shape.transformed(Transform::Rotate90).cells(),
+            &[(5, 5), (6, 5), (7, 5), (5, 6)]
+        );
+        assert_eq!(
+            l_shape.transformed(Transform::FlipHorizontal).cells(),
+            &[(6, 5), (6, 6), (5, 7), (6, 7)]
+        );
+        assert_eq!(
+            l_shape.transformed(Transform::Transpose).cells(),
+            &[(5, 5), (6, 5), (7, 5), (7, 6)]
+        );
+    }
+
+    #[test]
+    fn test_stepping_grows_past_bounds() {
+        let blinker = Pattern::new(vec![(0, 0), (1, 0), (2, 0)]);
+
+        let next = blinker.step();
+
+        assert_eq!(next.cells(), &[(1, -1), (1, 0), (1, 1)]);
+        assert_eq!(next.step(), blinker);
+    }
+
+    #[test]
+    fn test_pattern_to_grid_is_centered() {
+        let pattern = Pattern::new(vec![(-5, -5), (-4, -5), (-3, -5)]);
+
+        let grid = pattern.to_grid(5, 5).unwrap();
+
+        assert_eq!(
+            grid.live_cells().collect::<Vec<_>>(),
+            vec![(1, 2), (2, 2), (3, 2)]
+        );
+    }
+
+    #[test]
+    fn test_pattern_too_large_for_grid() {
+        let pattern = Pattern::new(vec![(0, 0), (10, 0)]);
+
+        let result = pattern.to_grid(5, 5);
+
+        assert_eq!(
+            result.unwrap_err(),
+            PatternError::TooLarge {
+                width: 11,
+                height: 1,
+                grid_width: 5,
+                grid_height: 5,
+            }
+        );
+    }
+
+    fn grid_with_row(cells: &[(usize, usize)]) -> Grid {
+        let mut grid = Grid::new(4, 3);
+        for &cell in cells {
+            let _ = grid.set_cell_at_coord(cell, true);
+        }
+        grid
+    }
+
+    #[test]
+    fn test_pasting_combines_by_mode() {
+        let pattern = Pattern::new(vec![(0, 0), (2, 0)]);
+        let mut or = grid_with_row(&[(1, 1), (2, 1)]);
+        let mut xor = grid_with_row(&[(1, 1), (2, 1)]);
+        let mut overwrite = grid_with_row(&[(1, 1), (2, 1)]);
+
+        pattern.paste_onto(&mut or, (1, 1), PasteMode::Or);
+        pattern.paste_onto(&mut xor, (1, 1), PasteMode::Xor);
+        pattern.paste_onto(&mut overwrite, (1, 1), PasteMode::Overwrite);
+
+        assert_eq!(
+            or.live_cells().collect::<Vec<_>>(),
+            vec![(1, 1), (2, 1), (3, 1)]
+        );
+        assert_eq!(xor.live_cells().collect::<Vec<_>>(), vec![(2, 1), (3, 1)]);
+        assert_eq!(
+            overwrite.live_cells().collect::<Vec<_>>(),
+            vec![(1, 1), (3, 1)]
+        );
+    }
+
+    #[test]
+    fn test_pasting_clips_to_grid() {
+        let pattern = Pattern::new(vec![(-1, 0), (0, 0), (1, 0)]);
+        let mut grid = Grid::new(4, 3);
+
+        pattern.paste_onto(&mut grid, (3, 2), PasteMode::Or);
+
+        assert_eq!(grid.live_cells().collect::<Vec<_>>(), vec![(2, 2), (3, 2)]);
+    }
+}
@@ -0,0 +1,372 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    hash::{DefaultHasher, Hash, Hasher},
+};
+
+use crate::{
+    game::Game,
+    grid::{Grid, Region},
+    pattern::Pattern,
+};
+
+/// A run of generations that keeps coming round again, possibly shifted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    /// First generation of the repeating part.
+    pub start: u32,
+    pub period: u32,
+    /// How far the pattern moves each period; zero for oscillators.
+    pub shift: (i64, i64),
+}
+
+impl Cycle {
+    /// Speed in c/period notation with its direction, as in "c/4 diagonal",
+    /// or `None` when the pattern stays put.
+    pub fn speed(&self) -> Option<String> {
+        let (dx, dy) = (self.shift.0.abs(), self.shift.1.abs());
+        if (dx, dy) == (0, 0) {
+            return None;
+        }
+
+        let period = i64::from(self.period);
+        let (long, short) = (dx.max(dy), dx.min(dy));
+        let divisor = gcd(gcd(long, short), period);
+        let over = match period / divisor {
+            1 => String::new(),
+            denominator => format!("/{denominator}"),
+        };
+        Some(match (long / divisor, short / divisor) {
+            (1, 0) => format!("c{over} orthogonal"),
+            (n, 0) => format!("{n}c{over} orthogonal"),
+            (1, 1) => format!("c{over} diagonal"),
+            (n, m) if n == m => format!("{n}c{over} diagonal"),
+            (n, m) => format!("({n},{m})c{over} oblique"),
+        })
+    }
+}
+
+impl Display for Cycle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "period {} from gen {}", self.period, self.start)?;
+        if let Some(speed) = self.speed() {
+            write!(f, ", moving {speed}")?;
+        }
+        Ok(())
+    }
+}
+
+/// How a period search ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SearchOutcome {
+    Found(Cycle),
+    /// Nothing repeated within the generations allowed.
+    NotFound,
+}
+
+/// What a search runs: the whole game on its grid, or a pattern alone on an
+/// unbounded plane.
+#[derive(Clone)]
+enum Universe {
+    Grid(Game),
+    Plane { pattern: Pattern, generation: u32 },
+}
+
+impl Universe {
+    fn generation(&self) -> u32 {
+        match self {
+            Universe::Grid(game) => game.generation(),
+            Universe::Plane { generation, .. } => *generation,
+        }
+    }
+
+    fn pattern(&self) -> Pattern {
+        match self {
+            Universe::Grid(game) => Pattern::from_grid(&game.grid),
+            Universe::Plane { pattern, .. } => pattern.clone(),
+        }
+    }
+
+    fn step(&mut self) {
+        match self {
+            Universe::Grid(game) => {
+                game.tick();
+            }
+            Universe::Plane {
+                pattern,
+                generation,
+            } => {
+                *pattern = pattern.step();
+                *generation += 1;
+            }
+        }
+    }
+}
+
+/// Runs a game forward a few generations at a time, hashing each one, until
+/// a state comes round again. States are compared with their bounding box
+/// moved to the origin, so a pattern that returns elsewhere counts as a
+/// repeat. Only hashes are kept; a match is confirmed by replaying the
+/// earlier generation and comparing its cells.
+pub struct PeriodSearch {
+    first: Universe,
+    current: Universe,
+    last_generation: u32,
+    /// Every state seen, by the hash of its normalised cells.
+    seen: HashMap<u64, Vec<Sighting>>,
+}
+
+/// The generation a state was seen at, and where its bounding box was then.
+type Sighting = (u32, (i64, i64));
+
+impl PeriodSearch {
+    /// Searches the whole game, or with a `region` just its contents, alone
+    /// on an unbounded plane so spaceships are not lost off its edge.
+    pub fn new(game: &Game, region: Option<Region>, max_generations: u32) -> Self {
+        let first = match region {
+            Some(region) => Universe::Plane {
+                pattern: Pattern::from_grid(
+                    &game
+                        .grid
+                        .cropped(region)
+                        .expect("PeriodSearch: region outside of grid"),
+                ),
+                generation: game.generation(),
+            },
+            None => Universe::Grid(game.clone()),
+        };
+        PeriodSearch {
+            current: first.clone(),
+            last_generation: game.generation().saturating_add(max_generations),
+            first,
+            seen: HashMap::new(),
+        }
+    }
+
+    /// The generation about to be looked at.
+    pub fn generation(&self) -> u32 {
+        self.current.generation()
+    }
+
+    /// Looks at up to `max_steps` more generations, returning the outcome
+    /// once the search is over.
+    pub fn continue_search(&mut self, max_steps: u32) -> Option<SearchOutcome> {
+        for _ in 0..max_steps {
+            if let Some(cycle) = self.observe() {
+                return Some(SearchOutcome::Found(cycle));
+            }
+            if self.current.generation() >= self.last_generation {
+                return Some(SearchOutcome::NotFound);
+            }
+            self.current.step();
+        }
+        None
+    }
+
+    fn observe(&mut self) -> Option<Cycle> {
+        let generation = self.current.generation();
+        let pattern = self.current.pattern();
+        let corner = pattern.bounds().map_or((0, 0), |b| (b.min_x, b.min_y));
+        let normalized = pattern.normalized();
+
+        let candidates = self.seen.entry(pattern_hash(&normalized)).or_default();
+        let matching = candidates
+            .iter()
+            .find(|&&(start, _)| replay(&self.first, start).pattern().normalized() == normalized);
+        match matching {
+            Some(&(start, first_corner)) => Some(Cycle {
+                start,
+                period: generation - start,
+                shift: (corner.0 - first_corner.0, corner.1 - first_corner.1),
+            }),
+            None => {
+                candidates.push((generation, corner));
+                None
+            }
+        }
+    }
+}
+
+/// Runs a copy of `first` forward to `generation`.
+fn replay(first: &Universe, generation: u32) -> Universe {
+    let mut universe = first.clone();
+    while universe.generation() < generation {
+        universe.step();
+    }
+    universe
+}
+
+fn pattern_hash(pattern: &Pattern) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    pattern.cells().hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Hash of the grid's live cells where they are.
+pub fn state_hash(grid: &Grid) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    (grid.width(), grid.height()).hash(&mut hasher);
+    for cell in grid.live_cells() {
+        cell.hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::library::Library;
+
+    fn builtin(name: &str) -> Pattern {
+        Library::default()
+            .entries()
+            .find(|e| e.name == name)
+            .map(|e| e.pattern.clone())
+            .unwrap()
+    }
+
+    fn game_of(pattern: &Pattern) -> Game {
+        Game::new(pattern.to_grid(40, 40).unwrap())
+    }
+
+    fn find_period(game: &Game, region: Option<Region>, max_generations: u32) -> Option<Cycle> {
+        match game
+            .period_search(region, max_generations)
+            .continue_search(u32::MAX)?
+        {
+            SearchOutcome::Found(cycle) => Some(cycle),
+            SearchOutcome::NotFound => None,
+        }
+    }
+
+    fn oscillating(start: u32, period: u32) -> Option<Cycle> {
+        Some(Cycle {
+            start,
+            period,
+            shift: (0, 0),
+        })
+    }
+
+    #[test]
+    fn test_finds_oscillator_periods() {
+        let blinker = Pattern::new(vec![(0, 0), (1, 0), (2, 0)]);
+
+        let blinker = find_period(&game_of(&blinker), None, 100);
+        let pulsar = find_period(&game_of(&builtin("Pulsar")), None, 100);
+        let pentadecathlon = find_period(&game_of(&builtin("Pentadecathlon")), None, 100);
+
+        assert_eq!(blinker, oscillating(0, 2));
+        assert_eq!(pulsar, oscillating(0, 3));
+        assert_eq!(pentadecathlon, oscillating(0, 15));
+        assert_eq!(blinker.unwrap().speed(), None);
+    }
+
+    #[test]
+    fn test_cycle_starts_where_pattern_settles() {
+        let pre_block = Pattern::new(vec![(0, 0), (1, 0), (0, 1)]);
+        let game = game_of(&pre_block).with_generation(100);
+
+        let cycle = find_period(&game, None, 100);
+        let too_soon = find_period(&game, None, 1);
+
+        assert_eq!(cycle, oscillating(101, 1));
+        assert_eq!(too_soon, None);
+        assert_eq!(cycle.unwrap().to_string(), "period 1 from gen 101");
+    }
+
+    #[test]
+    fn test_selection_is_analysed_on_its_own() {
+        let mut grid = Grid::new(40, 20);
+        let blinker = [(2, 3), (3, 3), (4, 3)];
+        let glider = [(21, 10), (22, 11), (20, 12), (21, 12), (22, 12)];
+        for cell in blinker.into_iter().chain(glider) {
+            grid.set_cell_at_coord(cell, true).unwrap();
+        }
+        let game = Game::new(grid);
+        let around_blinker = Region::from_corners((1, 1), (5, 5));
+
+        let whole = find_period(&game, None, 3);
+        let region = find_period(&game, Some(around_blinker), 3);
+
+        assert_eq!(whole, None);
+        assert_eq!(region, oscillating(0, 2));
+    }
+
+    #[test]
+    fn test_finds_spaceship_speeds() {
+        let glider = find_period(&game_of(&builtin("Glider")), None, 100).unwrap();
+        let lwss = game_of(&builtin("LWSS"));
+        let selection = Region::from_corners((15, 15), (24, 24));
+
+        let lwss = find_period(&lwss, Some(selection), 100).unwrap();
+
+        assert_eq!((glider.period, glider.shift), (4, (1, 1)));
+        assert_eq!(glider.speed().as_deref(), Some("c/4 diagonal"));
+        assert_eq!((lwss.period, lwss.shift.0.abs()), (4, 2));
+        assert_eq!(lwss.speed().as_deref(), Some("c/2 orthogonal"));
+        assert_eq!(
+            glider.to_string(),
+            "period 4 from gen 0, moving c/4 diagonal"
+        );
+    }
+
+    #[test]
+    fn test_speed_notation() {
+        let speed = |shift, period| {
+            Cycle {
+                start: 0,
+                period,
+                shift,
+            }
+            .speed()
+        };
+
+        assert_eq!(speed((0, -1), 3).as_deref(), Some("c/3 orthogonal"));
+        assert_eq!(speed((2, 0), 3).as_deref(), Some("2c/3 orthogonal"));
+        assert_eq!(speed((-3, 3), 12).as_deref(), Some("c/4 diagonal"));
+        assert_eq!(speed((2, 1), 6).as_deref(), Some("(2,1)c/6 oblique"));
+        assert_eq!(speed((1, 0), 1).as_deref(), Some("c orthogonal"));
+    }
+
+    #[test]
+    fn test_hash_matches_are_confirmed_by_cells() {
+        let blinker = Pattern::new(vec![(0, 0), (1, 0), (2, 0)]);
+        let mut search = game_of(&blinker).period_search(None, 100);
+        // Pretend the upright phase collides with the starting state.
+        let upright = Pattern::new(vec![(0, 0), (0, 1), (0, 2)]);
+        search
+            .seen
+            .insert(pattern_hash(&upright), vec![(0, (0, 0))]);
+
+        let outcome = search.continue_search(100);
+
+        assert_eq!(
+            outcome,
+            Some(SearchOutcome::Found(oscillating(0, 2).unwrap()))
+        );
+    }
+
+    #[test]
+    fn test_search_runs_in_batches() {
+        let mut search = game_of(&builtin("Pentadecathlon")).period_search(None, 100);
+        let mut giving_up = game_of(&builtin("Acorn")).period_search(None, 10);
+
+        let first_batch = search.continue_search(8);
+        let paused_at = search.generation();
+        let second_batch = search.continue_search(8);
+        let gave_up = giving_up.continue_search(100);
+
+        assert_eq!(first_batch, None);
+        assert_eq!(paused_at, 8);
+        assert_eq!(
+            second_batch,
+            Some(SearchOutcome::Found(oscillating(0, 15).unwrap()))
+        );
+        assert_eq!(gave_up, Some(SearchOutcome::NotFound));
+        assert_eq!(giving_up.generation(), 10);
+    }
+}
@@ -0,0 +1,204 @@
+use std::{fmt::Display, fs, path::Path, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{game::Game, grid::Grid};
+
+/// Bumped whenever the file layout changes incompatibly.
+pub const VERSION: u32 = 1;
+
+/// Everything needed to reopen a game exactly as it was left.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Session {
+    pub version: u32,
+    pub width: usize,
+    pub height: usize,
+    pub live_cells: Vec<(usize, usize)>,
+    pub generation: u32,
+    pub interval_ms: u64,
+    pub zoom: f32,
+    pub scroll_offset: [f32; 2],
+}
+
+impl Session {
+    pub fn capture(game: &Game, interval: Duration, zoom: f32, scroll_offset: [f32; 2]) -> Self {
+        Session {
+            version: VERSION,
+            width: game.grid.width(),
+            height: game.grid.height(),
+            live_cells: game.grid.live_cells().collect(),
+            generation: game.generation(),
+            interval_ms: interval.as_millis() as u64,
+            zoom,
+            scroll_offset,
+        }
+    }
+
+    pub fn interval(&self) -> Duration {
+        Duration::from_millis(self.interval_ms)
+    }
+
+    /// The saved zoom brought within `(min, max)`; one that is not a number
+    /// falls back to 1.
+    pub fn zoom(&self, (min, max): (f32, f32)) -> f32 {
+        let zoom = if self.zoom.is_nan() { 1.0 } else { self.zoom };
+        zoom.clamp(min, max)
+    }
+
+    /// Rebuilds the game, rejecting grids larger than `max_size` before
+    /// allocating them.
+    pub fn to_game(&self, max_size: (usize, usize)) -> Result<Game, SessionError> {
+        if self.width == 0 || self.height == 0 {
+            return Err(SessionError::Invalid("grid has no cells".into()));
+        }
+        if self.width > max_size.0 || self.height > max_size.1 {
+            return Err(SessionError::Invalid(format!(
+                "grid is {}x{} but at most {}x{} is allowed",
+                self.width, self.height, max_size.0, max_size.1
+            )));
+        }
+
+        let mut grid = Grid::new(self.width, self.height);
+        for &(x, y) in &self.live_cells {
+            if x >= self.width || y >= self.height {
+                return Err(SessionError::Invalid(format!(
+                    "cell ({x}, {y}) lies outside the grid"
+                )));
+            }
+            grid.set_cell_at_coord((x, y), true)
+                .expect("to_game: cell outside of checked bounds");
+        }
+
+        Ok(Game::new(grid).with_generation(self.generation))
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("to_json: session is always serializable")
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, SessionError> {
+        #[derive(Deserialize)]
+        struct Header {
+            version: u32,
+        }
+
+        // Check the version first so newer files fail clearly rather than on
+        // whichever field changed.
+        let header: Header =
+            serde_json::from_str(json).map_err(|e| SessionError::Parse(e.to_string()))?;
+        if header.version != VERSION {
+            return Err(SessionError::UnsupportedVersion(header.version));
+        }
+
+        serde_json::from_str(json).map_err(|e| SessionError::Parse(e.to_string()))
+    }
+}
+
+pub fn save_session(path: &Path, session: &Session) -> Result<(), SessionError> {
+    fs::write(path, session.to_json()).map_err(|e| SessionError::Io(e.to_string()))
+}
+
+pub fn load_session(path: &Path) -> Result<Session, SessionError> {
+    let json = fs::read_to_string(path).map_err(|e| SessionError::Io(e.to_string()))?;
+    Session::from_json(&json)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SessionError {
+    Io(String),
+    Parse(String),
+    UnsupportedVersion(u32),
+    Invalid(String),
+}
+
+impl Display for SessionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SessionError::Io(message) => write!(f, "{message}"),
+            SessionError::Parse(message) => write!(f, "malformed session: {message}"),
+            SessionError::UnsupportedVersion(version) => {
+                write!(f, "unsupported session version {version}")
+            }
+            SessionError::Invalid(message) => write!(f, "invalid session: {message}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blinker_game() -> Game {
+        let mut grid = Grid::new(5, 4);
+        for x in 1..4 {
+            let _ = grid.set_cell_at_coord((x, 2), true);
+        }
+        let mut game = Game::new(grid);
+        game.tick();
+        game
+    }
+
+    #[test]
+    fn test_session_round_trips_through_json() {
+        let game = blinker_game();
+        let session = Session::capture(&game, Duration::from_millis(250), 1.5, [12.0, 3.0]);
+
+        let restored = Session::from_json(&session.to_json()).unwrap();
+        let restored_game = restored.to_game((5, 4)).unwrap();
+
+        assert_eq!(restored, session);
+        assert_eq!(restored.interval(), Duration::from_millis(250));
+        assert_eq!(restored_game.generation(), 1);
+        assert_eq!(
+            restored_game.grid.live_cells().collect::<Vec<_>>(),
+            vec![(2, 1), (2, 2), (2, 3)]
+        );
+    }
+
+    #[test]
+    fn test_loading_rejects_other_versions() {
+        let json = r#"{ "version": 99, "width": 1 }"#;
+
+        let result = Session::from_json(json);
+
+        assert_eq!(result, Err(SessionError::UnsupportedVersion(99)));
+    }
+
+    #[test]
+    fn test_restoring_rejects_cells_outside_grid() {
+        let mut session = Session::capture(&blinker_game(), Duration::ZERO, 1.0, [0.0, 0.0]);
+        session.live_cells.push((5, 0));
+
+        let result = session.to_game((100, 100));
+
+        assert!(matches!(result, Err(SessionError::Invalid(_))));
+    }
+
+    #[test]
+    fn test_restoring_keeps_zoom_in_range() {
+        let zoom_of = |zoom| {
+            Session::capture(&blinker_game(), Duration::ZERO, zoom, [0.0, 0.0]).zoom((0.5, 4.0))
+        };
+
+        assert_eq!(zoom_of(2.0), 2.0);
+        assert_eq!(zoom_of(0.0), 0.5);
+        assert_eq!(zoom_of(-3.0), 0.5);
+        assert_eq!(zoom_of(f32::INFINITY), 4.0);
+        assert_eq!(zoom_of(f32::NAN), 1.0);
+    }
+
+    #[test]
+    fn test_restoring_rejects_oversized_grid() {
+        let mut session = Session::capture(&blinker_game(), Duration::ZERO, 1.0, [0.0, 0.0]);
+        session.width = 4_000_000_000;
+
+        let result = session.to_game((500, 300));
+
+        assert_eq!(
+            result.err(),
+            Some(SessionError::Invalid(
+                "grid is 4000000000x4 but at most 500x300 is allowed".into()
+            ))
+        );
+    }
+}
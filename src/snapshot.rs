@@ -0,0 +1,133 @@
+use crate::{game::Game, grid::Grid};
+
+/// A game's cells packed one bit per cell, with its generation. The words
+/// are run-length encoded: only runs of non-zero words are kept, each behind
+/// a short header, so empty stretches cost nothing and a busy grid costs
+/// little more than a plain bitmap.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Snapshot {
+    width: usize,
+    height: usize,
+    generation: u32,
+    /// First word and length of each run of non-zero words, ascending.
+    runs: Vec<(u32, u32)>,
+    /// The words of every run, one after another.
+    words: Vec<u64>,
+}
+
+impl Snapshot {
+    pub fn capture(game: &Game) -> Self {
+        let grid = &game.grid;
+        let mut runs: Vec<(u32, u32)> = Vec::new();
+        let mut words: Vec<u64> = Vec::new();
+        let mut last_word = None;
+        // Live cells come in index order, so words fill in order too.
+        for (x, y) in grid.live_cells() {
+            let i = y * grid.width() + x;
+            let word = (i / 64) as u32;
+            if last_word != Some(word) {
+                match runs.last_mut() {
+                    Some((start, len)) if *start + *len == word => *len += 1,
+                    _ => runs.push((word, 1)),
+                }
+                words.push(0);
+                last_word = Some(word);
+            }
+            *words.last_mut().expect("capture: word was just pushed") |= 1 << (i % 64);
+        }
+
+        Snapshot {
+            width: grid.width(),
+            height: grid.height(),
+            generation: game.generation(),
+            runs,
+            words,
+        }
+    }
+
+    pub fn restore(&self) -> Game {
+        let mut cells = vec![false; self.width * self.height];
+        let positions = self
+            .runs
+            .iter()
+            .flat_map(|&(start, len)| start as usize..(start + len) as usize);
+        for (word, &bits) in positions.zip(&self.words) {
+            for bit in 0..64 {
+                if bits & (1 << bit) != 0 {
+                    cells[word * 64 + bit] = true;
+                }
+            }
+        }
+
+        let mut grid = Grid::new(self.width, self.height);
+        grid.set_cells(cells)
+            .expect("restore: snapshot size matches grid");
+        Game::new(grid).with_generation(self.generation)
+    }
+
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+
+    /// Memory held, counting the snapshot itself so even an empty grid
+    /// uses up some of a budget.
+    pub fn size_bytes(&self) -> usize {
+        size_of::<Snapshot>()
+            + self.runs.len() * size_of::<(u32, u32)>()
+            + self.words.len() * size_of::<u64>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::soup::SoupOptions;
+
+    #[test]
+    fn test_snapshot_round_trips_cells_and_generation() {
+        let mut grid = Grid::new(10, 10);
+        for cell in [(0, 0), (3, 6), (9, 9)] {
+            grid.set_cell_at_coord(cell, true).unwrap();
+        }
+        let game = Game::new(grid).with_generation(42);
+
+        let restored = Snapshot::capture(&game).restore();
+
+        assert_eq!(
+            restored.grid.live_cells().collect::<Vec<_>>(),
+            vec![(0, 0), (3, 6), (9, 9)]
+        );
+        assert_eq!(restored.generation(), 42);
+    }
+
+    #[test]
+    fn test_snapshot_keeps_only_live_words() {
+        let mut grid = Grid::new(100, 100);
+        grid.set_cell_at_coord((50, 50), true).unwrap();
+        grid.set_cell_at_coord((51, 50), true).unwrap();
+
+        let snapshot = Snapshot::capture(&Game::new(grid));
+
+        assert_eq!(snapshot.size_bytes(), size_of::<Snapshot>() + 16);
+        assert_eq!(
+            Snapshot::capture(&Game::new(Grid::new(100, 100))).size_bytes(),
+            size_of::<Snapshot>()
+        );
+    }
+
+    #[test]
+    fn test_dense_snapshot_costs_no_more_than_a_bitmap() {
+        let mut grid = Grid::new(100, 100);
+        grid.randomize(&SoupOptions::default());
+        let game = Game::new(grid);
+        let bitmap_bytes = (100 * 100usize).div_ceil(64) * size_of::<u64>();
+
+        let snapshot = Snapshot::capture(&game);
+
+        assert!(snapshot.size_bytes() <= size_of::<Snapshot>() + bitmap_bytes + 8);
+        assert_eq!(
+            snapshot.restore().grid.live_cells().collect::<Vec<_>>(),
+            game.grid.live_cells().collect::<Vec<_>>()
+        );
+    }
+}
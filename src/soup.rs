@@ -0,0 +1,151 @@
+use crate::pattern::Transform;
+
+/// Symmetry imposed on a random soup, named as in apgsearch.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Symmetry {
+    #[default]
+    C1,
+    /// Unchanged by a half turn.
+    C2,
+    /// Unchanged by quarter turns.
+    C4,
+    /// Mirrored left to right.
+    D2,
+    /// Mirrored left to right and top to bottom.
+    D4,
+    /// Unchanged by every rotation and reflection of the square.
+    D8,
+}
+
+impl Symmetry {
+    pub const ALL: [Symmetry; 6] = [
+        Symmetry::C1,
+        Symmetry::C2,
+        Symmetry::C4,
+        Symmetry::D2,
+        Symmetry::D4,
+        Symmetry::D8,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Symmetry::C1 => "C1",
+            Symmetry::C2 => "C2",
+            Symmetry::C4 => "C4",
+            Symmetry::D2 => "D2",
+            Symmetry::D4 => "D4",
+            Symmetry::D8 => "D8",
+        }
+    }
+
+    /// The group of transforms a soup must be unchanged by.
+    pub fn transforms(self) -> &'static [Transform] {
+        match self {
+            Symmetry::C1 => &[Transform::Identity],
+            Symmetry::C2 => &[Transform::Identity, Transform::Rotate180],
+            Symmetry::C4 => &[
+                Transform::Identity,
+                Transform::Rotate90,
+                Transform::Rotate180,
+                Transform::Rotate270,
+            ],
+            Symmetry::D2 => &[Transform::Identity, Transform::FlipHorizontal],
+            Symmetry::D4 => &[
+                Transform::Identity,
+                Transform::FlipHorizontal,
+                Transform::FlipVertical,
+                Transform::Rotate180,
+            ],
+            Symmetry::D8 => &Transform::ALL,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SoupOptions {
+    /// Chance of each cell being alive, from 0 to 1.
+    pub density: f64,
+    pub seed: u64,
+    pub symmetry: Symmetry,
+}
+
+impl Default for SoupOptions {
+    fn default() -> Self {
+        Self {
+            density: 0.5,
+            seed: 1,
+            symmetry: Symmetry::default(),
+        }
+    }
+}
+
+/// Small deterministic generator, so a seed always gives the same soup.
+#[derive(Clone, Debug)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// True with the given probability.
+    pub fn chance(&mut self, probability: f64) -> bool {
+        let unit = (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        unit < probability
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generator_is_reproducible() {
+        let mut a = SplitMix64::new(42);
+        let mut b = SplitMix64::new(42);
+        let mut c = SplitMix64::new(43);
+
+        let first = (0..4).map(|_| a.next_u64()).collect::<Vec<_>>();
+
+        assert_eq!(first, (0..4).map(|_| b.next_u64()).collect::<Vec<_>>());
+        assert_ne!(first, (0..4).map(|_| c.next_u64()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_chance_follows_probability() {
+        let mut rng = SplitMix64::new(7);
+
+        let hits = (0..10_000).filter(|_| rng.chance(0.25)).count();
+
+        assert!((2_300..2_700).contains(&hits));
+        assert!(!rng.chance(0.0));
+        assert!(rng.chance(1.0));
+    }
+
+    #[test]
+    fn test_symmetry_groups_are_closed() {
+        for symmetry in Symmetry::ALL {
+            let group = symmetry.transforms();
+            for &a in group {
+                for &b in group {
+                    let image = b.apply(a.apply((1, 2)));
+                    assert!(
+                        group.iter().any(|t| t.apply((1, 2)) == image),
+                        "{} is not closed",
+                        symmetry.name()
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,203 @@
+use std::{collections::VecDeque, fmt::Display};
+
+use crate::{game::Game, period::state_hash};
+
+/// Which kinds of settling pause playback.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AutoPause {
+    pub extinct: bool,
+    pub stable: bool,
+    pub periodic: bool,
+    /// Longest period looked for when `periodic` is set.
+    pub max_period: u32,
+}
+
+impl Default for AutoPause {
+    fn default() -> Self {
+        AutoPause {
+            extinct: true,
+            stable: true,
+            periodic: true,
+            max_period: 30,
+        }
+    }
+}
+
+/// How the universe settled, with the generation it did so at.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Settled {
+    Extinct(u32),
+    /// Nothing has changed since this generation.
+    Stable(u32),
+    /// The generation at which the cycle begins, and its length.
+    Periodic {
+        period: u32,
+        since: u32,
+    },
+}
+
+impl Display for Settled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Settled::Extinct(generation) => write!(f, "extinct at gen {generation}"),
+            Settled::Stable(generation) => write!(f, "stable at gen {generation}"),
+            Settled::Periodic { period, since } => write!(f, "period {period} after gen {since}"),
+        }
+    }
+}
+
+/// Watches a game as it is ticked and reports once when it settles. Keeps
+/// the hashes of the last few generations to spot a state coming round again.
+#[derive(Debug, Default)]
+pub struct StabilityWatcher {
+    /// Hashes of the generations before `last`, oldest first.
+    recent: VecDeque<u64>,
+    last: Option<u32>,
+    reported: bool,
+}
+
+impl StabilityWatcher {
+    /// Notes the game about to be ticked. Watching starts afresh from it when
+    /// it was `edited` or does not follow on from the last generation seen.
+    pub fn resume(&mut self, game: &Game, edited: bool, options: &AutoPause) {
+        if !edited && self.last == Some(game.generation()) {
+            return;
+        }
+        self.recent.clear();
+        self.reported = false;
+        self.last = Some(game.generation());
+        if options.periodic {
+            self.recent.push_back(state_hash(&game.grid));
+        }
+    }
+
+    /// Looks at the game just ticked; `changed` is what the tick returned.
+    /// Reports the first sign of settling since watching started.
+    pub fn observe(&mut self, game: &Game, changed: bool, options: &AutoPause) -> Option<Settled> {
+        let generation = game.generation();
+        self.last = Some(generation);
+
+        // An empty universe is only news on the tick that emptied it, so an
+        // empty grid can be played while drawing on it.
+        let settled = if game.grid.live_cells().next().is_none() {
+            (options.extinct && changed).then_some(Settled::Extinct(generation))
+        } else if options.stable && !changed {
+            Some(Settled::Stable(generation - 1))
+        } else if options.periodic {
+            self.find_period(state_hash(&game.grid), generation, options.max_period)
+        } else {
+            None
+        };
+
+        if self.reported {
+            return None;
+        }
+        self.reported = settled.is_some();
+        settled
+    }
+
+    fn find_period(&mut self, hash: u64, generation: u32, max_period: u32) -> Option<Settled> {
+        let len = self.recent.len();
+        // A period of one is a still state, reported as stable instead.
+        let period = (1..=len.min(max_period as usize))
+            .find(|&period| self.recent[len - period] == hash)
+            .filter(|&period| period > 1)
+            .map(|period| period as u32);
+
+        self.recent.push_back(hash);
+        while self.recent.len() > max_period as usize {
+            self.recent.pop_front();
+        }
+        period.map(|period| Settled::Periodic {
+            period,
+            since: generation - period,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+
+    fn game_with_cells(cells: &[(usize, usize)]) -> Game {
+        let mut grid = Grid::new(8, 8);
+        for &cell in cells {
+            grid.set_cell_at_coord(cell, true).unwrap();
+        }
+        Game::new(grid)
+    }
+
+    /// Ticks until the watcher reports, giving up after `limit` generations.
+    fn watch(game: &mut Game, options: &AutoPause, limit: u32) -> Option<Settled> {
+        let mut watcher = StabilityWatcher::default();
+        (0..limit).find_map(|_| {
+            watcher.resume(game, false, options);
+            let changed = game.tick();
+            watcher.observe(game, changed, options)
+        })
+    }
+
+    #[test]
+    fn test_watcher_reports_extinct_stable_and_periodic() {
+        let options = AutoPause::default();
+        let mut lonely = game_with_cells(&[(1, 1), (4, 4)]);
+        let mut pond = game_with_cells(&[(1, 1), (2, 1), (1, 2)]);
+        let mut blinker = game_with_cells(&[(1, 2), (2, 2), (3, 2)]);
+
+        let lonely = watch(&mut lonely, &options, 10);
+        let pond = watch(&mut pond, &options, 10);
+        let blinker = watch(&mut blinker, &options, 10);
+
+        assert_eq!(lonely, Some(Settled::Extinct(1)));
+        assert_eq!(pond, Some(Settled::Stable(1)));
+        assert_eq!(
+            blinker,
+            Some(Settled::Periodic {
+                period: 2,
+                since: 0
+            })
+        );
+        assert_eq!(blinker.unwrap().to_string(), "period 2 after gen 0");
+    }
+
+    #[test]
+    fn test_watcher_reports_once_until_edited() {
+        let options = AutoPause::default();
+        let mut watcher = StabilityWatcher::default();
+        let mut game = game_with_cells(&[(1, 1), (2, 1), (1, 2), (2, 2)]);
+        let mut step = |game: &mut Game, edited| {
+            watcher.resume(game, edited, &options);
+            let changed = game.tick();
+            watcher.observe(game, changed, &options)
+        };
+
+        let first = step(&mut game, false);
+        let again = step(&mut game, false);
+        let after_edit = step(&mut game, true);
+
+        assert_eq!(first, Some(Settled::Stable(0)));
+        assert_eq!(again, None);
+        assert_eq!(after_edit, Some(Settled::Stable(2)));
+    }
+
+    #[test]
+    fn test_watcher_ignores_disabled_kinds() {
+        let options = AutoPause {
+            stable: false,
+            max_period: 1,
+            ..AutoPause::default()
+        };
+        let mut blinker = game_with_cells(&[(1, 2), (2, 2), (3, 2)]);
+        let mut block = game_with_cells(&[(1, 1), (2, 1), (1, 2), (2, 2)]);
+        let mut empty = game_with_cells(&[]);
+
+        let blinker = watch(&mut blinker, &options, 10);
+        let block = watch(&mut block, &options, 10);
+        let empty = watch(&mut empty, &AutoPause::default(), 10);
+
+        assert_eq!(blinker, None);
+        assert_eq!(block, None);
+        assert_eq!(empty, None);
+    }
+}
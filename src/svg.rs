@@ -0,0 +1,167 @@
+use std::{fmt::Write, fs, io, path::Path};
+
+use crate::{bitmap::ImageStyle, grid::Grid};
+
+const CAPTION_HEIGHT: u32 = 24;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum CellShape {
+    #[default]
+    Square,
+    Circle,
+}
+
+impl CellShape {
+    pub const ALL: [CellShape; 2] = [CellShape::Square, CellShape::Circle];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            CellShape::Square => "Square",
+            CellShape::Circle => "Circle",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct SvgOptions {
+    pub style: ImageStyle,
+    pub shape: CellShape,
+    pub caption: bool,
+}
+
+/// Renders the grid as SVG, with `caption` written under the cells when the
+/// options ask for one.
+pub fn render(grid: &Grid, options: &SvgOptions, caption: &str) -> String {
+    let size = options.style.cell_size.max(1);
+    let width = grid.width() as u32 * size;
+    let cells_height = grid.height() as u32 * size;
+    let height = cells_height + if options.caption { CAPTION_HEIGHT } else { 0 };
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+    );
+    let _ = writeln!(
+        out,
+        r#"<rect width="{width}" height="{height}" fill="{}"/>"#,
+        hex(options.style.dead)
+    );
+
+    let _ = writeln!(out, r#"<g fill="{}">"#, hex(options.style.alive));
+    for (x, y) in grid.live_cells() {
+        let (left, top) = (x as u32 * size, y as u32 * size);
+        let _ = match options.shape {
+            CellShape::Square => writeln!(
+                out,
+                r#"<rect x="{left}" y="{top}" width="{size}" height="{size}"/>"#
+            ),
+            CellShape::Circle => {
+                let r = size as f32 / 2.0;
+                writeln!(
+                    out,
+                    r#"<circle cx="{}" cy="{}" r="{r}"/>"#,
+                    left as f32 + r,
+                    top as f32 + r
+                )
+            }
+        };
+    }
+    out.push_str("</g>\n");
+
+    if let Some(color) = options.style.grid_lines {
+        let _ = writeln!(out, r#"<g stroke="{}" stroke-width="1">"#, hex(color));
+        for x in 0..=grid.width() as u32 {
+            let px = x * size;
+            let _ = writeln!(
+                out,
+                r#"<line x1="{px}" y1="0" x2="{px}" y2="{cells_height}"/>"#
+            );
+        }
+        for y in 0..=grid.height() as u32 {
+            let py = y * size;
+            let _ = writeln!(out, r#"<line x1="0" y1="{py}" x2="{width}" y2="{py}"/>"#);
+        }
+        out.push_str("</g>\n");
+    }
+
+    if options.caption {
+        let _ = writeln!(
+            out,
+            r#"<text x="4" y="{}" font-family="monospace" font-size="14" fill="{}">{}</text>"#,
+            cells_height + CAPTION_HEIGHT - 7,
+            hex(options.style.alive),
+            escape(caption)
+        );
+    }
+
+    out.push_str("</svg>\n");
+    out
+}
+
+pub fn export_svg(path: &Path, grid: &Grid, options: &SvgOptions, caption: &str) -> io::Result<()> {
+    fs::write(path, render(grid, options, caption))
+}
+
+fn hex([r, g, b]: [u8; 3]) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid_with_cell() -> Grid {
+        let mut grid = Grid::new(3, 2);
+        let _ = grid.set_cell_at_coord((2, 1), true);
+        grid
+    }
+
+    #[test]
+    fn test_rendering_square_cells() {
+        let options = SvgOptions {
+            style: ImageStyle {
+                cell_size: 10,
+                ..ImageStyle::default()
+            },
+            ..SvgOptions::default()
+        };
+
+        let svg = render(&grid_with_cell(), &options, "");
+
+        assert!(
+            svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="30" height="20""#)
+        );
+        assert!(svg.contains(r#"<rect x="20" y="10" width="10" height="10"/>"#));
+        assert!(!svg.contains("<line"));
+        assert!(!svg.contains("<text"));
+    }
+
+    #[test]
+    fn test_rendering_circles_grid_lines_and_caption() {
+        let options = SvgOptions {
+            style: ImageStyle {
+                cell_size: 10,
+                alive: [255, 0, 0],
+                grid_lines: Some([0, 0, 255]),
+                ..ImageStyle::default()
+            },
+            shape: CellShape::Circle,
+            caption: true,
+        };
+
+        let svg = render(&grid_with_cell(), &options, "gen: 3 <x>");
+
+        assert!(svg.contains(r#"height="44""#));
+        assert!(svg.contains(r##"<g fill="#ff0000">"##));
+        assert!(svg.contains(r#"<circle cx="25" cy="15" r="5"/>"#));
+        assert_eq!(svg.matches("<line").count(), 4 + 3);
+        assert!(svg.contains("gen: 3 &lt;x&gt;</text>"));
+    }
+}
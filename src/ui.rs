@@ -1,8 +1,29 @@
-use crate::grid::Grid;
+use crate::{
+    draw::DrawSymmetry,
+    grid::{Grid, Region},
+    pattern::Pattern,
+};
+
+pub mod bookmarks;
+pub mod gif_export;
+pub mod identify;
+pub mod image_file;
+pub mod library;
+pub mod pattern_file;
+pub mod randomize;
+pub mod resize;
+pub mod run;
+pub mod session_file;
+pub mod settings;
+pub mod timeline;
 
 pub struct GridView<'a> {
     grid: &'a Grid,
     cell_size_px: f32,
+    preview: Option<&'a Pattern>,
+    selection: Option<Region>,
+    /// Drawing symmetry and its centre in half cells.
+    symmetry: Option<(DrawSymmetry, (i64, i64))>,
 }
 
 pub struct GridViewResult {
@@ -12,15 +33,74 @@ pub struct GridViewResult {
 
 #[derive(Debug)]
 pub enum PointerGridEvent {
-    Hovered { cell: (usize, usize) },
-    LeftClick { cell: (usize, usize) },
-    RightClick { cell: (usize, usize) },
-    BothClick { cell: (usize, usize) },
+    Hovered {
+        cell: (usize, usize),
+    },
+    LeftClick {
+        cell: (usize, usize),
+    },
+    RightClick {
+        cell: (usize, usize),
+    },
+    BothClick {
+        cell: (usize, usize),
+    },
+    /// Primary press with Shift held.
+    SelectStart {
+        cell: (usize, usize),
+    },
+    /// Pointer moved while a primary or secondary drag is in progress,
+    /// clamped to the grid.
+    DragMove {
+        cell: (usize, usize),
+    },
+    /// Primary or secondary button released after a press on the grid,
+    /// dragged or not.
+    DragEnd {
+        cell: (usize, usize),
+    },
+}
+
+impl PointerGridEvent {
+    pub fn cell(&self) -> (usize, usize) {
+        match *self {
+            PointerGridEvent::Hovered { cell }
+            | PointerGridEvent::LeftClick { cell }
+            | PointerGridEvent::RightClick { cell }
+            | PointerGridEvent::BothClick { cell }
+            | PointerGridEvent::SelectStart { cell }
+            | PointerGridEvent::DragMove { cell }
+            | PointerGridEvent::DragEnd { cell } => cell,
+        }
+    }
 }
 
 impl<'a> GridView<'a> {
     pub fn new(grid: &'a Grid, cell_size_px: f32) -> Self {
-        Self { grid, cell_size_px }
+        Self {
+            grid,
+            cell_size_px,
+            preview: None,
+            selection: None,
+            symmetry: None,
+        }
+    }
+
+    pub fn with_selection(mut self, selection: Option<Region>) -> Self {
+        self.selection = selection;
+        self
+    }
+
+    /// Overlays the axes of the drawing symmetry.
+    pub fn with_symmetry(mut self, symmetry: DrawSymmetry, centre: (i64, i64)) -> Self {
+        self.symmetry = (symmetry != DrawSymmetry::None).then_some((symmetry, centre));
+        self
+    }
+
+    /// Draws a translucent pattern around the cell under the pointer.
+    pub fn with_preview(mut self, preview: Option<&'a Pattern>) -> Self {
+        self.preview = preview;
+        self
     }
 
     pub fn show(self, ui: &mut egui::Ui) -> GridViewResult {
@@ -37,41 +117,51 @@ impl<'a> GridView<'a> {
         let origin = rect.min;
         let painter = ui.painter_at(rect);
 
-        let pointer_event = if response.hovered() {
-            ui.input(|i| {
-                i.pointer.hover_pos().map(|pointer_pos| {
-                    let local_position = pointer_pos - origin;
-                    let cell = (
-                        (local_position.x / cell_size_px).floor() as usize,
-                        (local_position.y / cell_size_px).floor() as usize,
-                    );
-
-                    let left = i.pointer.button_pressed(egui::PointerButton::Primary);
-                    let right = i.pointer.button_pressed(egui::PointerButton::Secondary);
-
-                    match (left, right) {
-                        (true, true) => PointerGridEvent::BothClick { cell },
-                        (true, false) => PointerGridEvent::LeftClick { cell },
-                        (false, true) => PointerGridEvent::RightClick { cell },
-                        (false, false) => PointerGridEvent::Hovered { cell },
-                    }
-                })
-            })
-        } else {
-            None
+        let to_cell = |pos: egui::Pos2| {
+            let local_position = pos - origin;
+            let x = (local_position.x / cell_size_px).floor() as i64;
+            let y = (local_position.y / cell_size_px).floor() as i64;
+            (
+                x.clamp(0, grid_width as i64 - 1) as usize,
+                y.clamp(0, grid_height as i64 - 1) as usize,
+            )
         };
 
+        let buttons = [egui::PointerButton::Primary, egui::PointerButton::Secondary];
+        let pointer_event = ui.input(|i| {
+            let cell = to_cell(i.pointer.interact_pos()?);
+
+            if buttons
+                .iter()
+                .any(|&b| response.drag_stopped_by(b) || response.clicked_by(b))
+            {
+                return Some(PointerGridEvent::DragEnd { cell });
+            }
+            if buttons.iter().any(|&b| response.dragged_by(b)) {
+                return Some(PointerGridEvent::DragMove { cell });
+            }
+            if !response.hovered() {
+                return None;
+            }
+
+            let left = i.pointer.button_pressed(egui::PointerButton::Primary);
+            let right = i.pointer.button_pressed(egui::PointerButton::Secondary);
+
+            Some(match (left, right) {
+                (true, true) => PointerGridEvent::BothClick { cell },
+                (true, false) if i.modifiers.shift => PointerGridEvent::SelectStart { cell },
+                (true, false) => PointerGridEvent::LeftClick { cell },
+                (false, true) => PointerGridEvent::RightClick { cell },
+                (false, false) => PointerGridEvent::Hovered { cell },
+            })
+        });
+
         // Draw alive cells and hovered/clicked cell
         for y in 0..grid_height {
             for x in 0..grid_width {
                 let cell_alive = self.grid.get_cell_at_coord((x, y)).unwrap_or(false);
-                let cell_hovered = match pointer_event {
-                    Some(PointerGridEvent::Hovered { cell })
-                    | Some(PointerGridEvent::LeftClick { cell })
-                    | Some(PointerGridEvent::RightClick { cell })
-                    | Some(PointerGridEvent::BothClick { cell }) => cell == (x, y),
-                    None => false,
-                };
+                let cell_hovered =
+                    pointer_event.as_ref().map(PointerGridEvent::cell) == Some((x, y));
 
                 if cell_alive || cell_hovered {
                     let r = cell_rect(origin, cell_size_px, x, y);
@@ -85,6 +175,33 @@ impl<'a> GridView<'a> {
             }
         }
 
+        // Draw the floating preview around the pointer's cell
+        if let (Some(preview), Some(event)) = (self.preview, &pointer_event) {
+            let cell = event.cell();
+            let color = ui.visuals().selection.bg_fill.gamma_multiply(0.7);
+            for &(dx, dy) in preview.cells() {
+                let target = (cell.0 as i64 + dx, cell.1 as i64 + dy);
+                if let Some((x, y)) = self.grid.checked_coord(target) {
+                    painter.rect_filled(cell_rect(origin, cell_size_px, x, y), 0.0, color);
+                }
+            }
+        }
+
+        // Draw the selection marquee
+        if let Some(region) = self.selection {
+            let min = cell_rect(origin, cell_size_px, region.x, region.y).min;
+            let size = egui::vec2(region.width as f32, region.height as f32) * cell_size_px;
+            let marquee = egui::Rect::from_min_size(min, size);
+            let selection = ui.visuals().selection;
+            painter.rect_filled(marquee, 0.0, selection.bg_fill.gamma_multiply(0.25));
+            painter.rect_stroke(
+                marquee,
+                0.0,
+                egui::Stroke::new(2.0, selection.stroke.color),
+                egui::StrokeKind::Inside,
+            );
+        }
+
         // Draw grid lines
         let stroke = egui::Stroke::new(1.0, ui.visuals().weak_text_color());
 
@@ -105,6 +222,36 @@ impl<'a> GridView<'a> {
             painter.line_segment([egui::pos2(origin.x, ya), egui::pos2(xb, ya)], stroke);
         }
 
+        // Draw the symmetry axes through the centre
+        if let Some((symmetry, (cx, cy))) = self.symmetry {
+            let centre =
+                origin + egui::vec2(cx as f32 / 2.0 + 0.5, cy as f32 / 2.0 + 0.5) * cell_size_px;
+            let stroke = egui::Stroke::new(1.5, ui.visuals().warn_fg_color);
+            let reach = rect.width() + rect.height();
+
+            let mut axes = Vec::new();
+            if matches!(
+                symmetry,
+                DrawSymmetry::Horizontal | DrawSymmetry::Both | DrawSymmetry::Eightfold
+            ) {
+                axes.push(egui::vec2(0.0, 1.0));
+            }
+            if matches!(
+                symmetry,
+                DrawSymmetry::Vertical | DrawSymmetry::Both | DrawSymmetry::Eightfold
+            ) {
+                axes.push(egui::vec2(1.0, 0.0));
+            }
+            if symmetry == DrawSymmetry::Eightfold {
+                axes.push(egui::vec2(1.0, 1.0));
+                axes.push(egui::vec2(1.0, -1.0));
+            }
+            for axis in axes {
+                painter.line_segment([centre - axis * reach, centre + axis * reach], stroke);
+            }
+            painter.circle_stroke(centre, cell_size_px * 0.4, stroke);
+        }
+
         GridViewResult {
             response,
             pointer_event,
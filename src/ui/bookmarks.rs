@@ -0,0 +1,83 @@
+use crate::game_loop::Bookmark;
+
+#[derive(Default)]
+pub struct BookmarksPanel {
+    open: bool,
+    name: String,
+}
+
+pub enum BookmarkAction {
+    Add(String),
+    Jump(usize),
+    Remove(usize),
+}
+
+impl BookmarksPanel {
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+    }
+
+    /// Shows the panel along the right edge of `ui`; call it before laying
+    /// out the rest of the game.
+    pub fn show_inside(
+        &mut self,
+        ui: &mut egui::Ui,
+        bookmarks: &[Bookmark],
+        generation: u32,
+    ) -> Option<BookmarkAction> {
+        if !self.open {
+            return None;
+        }
+        let mut action = None;
+
+        egui::SidePanel::right("bookmarks")
+            .resizable(false)
+            .show_inside(ui, |ui| {
+                ui.heading("Bookmarks");
+                ui.horizontal(|ui| {
+                    let hint = format!("gen {generation}");
+                    let field = ui.add(
+                        egui::TextEdit::singleline(&mut self.name)
+                            .hint_text(&hint)
+                            .desired_width(100.0),
+                    );
+                    let entered =
+                        field.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                    if ui.button("Add").clicked() || entered {
+                        let name = match self.name.trim() {
+                            "" => hint,
+                            name => name.to_owned(),
+                        };
+                        action = Some(BookmarkAction::Add(name));
+                        self.name.clear();
+                    }
+                });
+                ui.separator();
+
+                if bookmarks.is_empty() {
+                    ui.weak("No bookmarks yet");
+                }
+
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    egui::Grid::new("bookmark_list").show(ui, |ui| {
+                        for (i, bookmark) in bookmarks.iter().enumerate() {
+                            if ui
+                                .button(&bookmark.name)
+                                .on_hover_text("Jump back here")
+                                .clicked()
+                            {
+                                action = Some(BookmarkAction::Jump(i));
+                            }
+                            ui.weak(format!("gen {}", bookmark.generation()));
+                            if ui.small_button("✖").on_hover_text("Remove").clicked() {
+                                action = Some(BookmarkAction::Remove(i));
+                            }
+                            ui.end_row();
+                        }
+                    });
+                });
+            });
+
+        action
+    }
+}
@@ -0,0 +1,71 @@
+use std::path::PathBuf;
+
+use crate::animation::GifOptions;
+
+#[derive(Default)]
+pub struct GifExportWindow {
+    open: bool,
+    path: String,
+    options: GifOptions,
+}
+
+pub enum GifExportAction {
+    Export(PathBuf, GifOptions),
+}
+
+impl GifExportWindow {
+    pub fn open(&mut self) {
+        self.open = true;
+    }
+
+    /// `progress` is the running export's completion, if one is in flight.
+    pub fn show(&mut self, ctx: &egui::Context, progress: Option<f32>) -> Option<GifExportAction> {
+        let mut action = None;
+
+        egui::Window::new("Export GIF")
+            .open(&mut self.open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Path:");
+                    ui.text_edit_singleline(&mut self.path);
+                });
+
+                let options = &mut self.options;
+                ui.horizontal(|ui| {
+                    ui.label("Generations:");
+                    ui.add(egui::DragValue::new(&mut options.generations).range(1..=10_000));
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Frame delay (ms):");
+                    ui.add(egui::DragValue::new(&mut options.frame_delay_ms).range(10..=5_000));
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Cell size:");
+                    ui.add(egui::DragValue::new(&mut options.style.cell_size).range(1..=32));
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Alive:");
+                    ui.color_edit_button_srgb(&mut options.style.alive);
+                    ui.label("Dead:");
+                    ui.color_edit_button_srgb(&mut options.style.dead);
+                });
+                ui.checkbox(&mut options.crop, "Crop to pattern");
+                ui.checkbox(&mut options.looping, "Loop");
+
+                match progress {
+                    Some(fraction) => {
+                        ui.add(egui::ProgressBar::new(fraction).show_percentage());
+                    }
+                    None => {
+                        if ui.button("Export").clicked() {
+                            let path = PathBuf::from(self.path.trim());
+                            action = Some(GifExportAction::Export(path, options.clone()));
+                        }
+                    }
+                }
+            });
+
+        action
+    }
+}
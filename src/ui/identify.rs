@@ -0,0 +1,54 @@
+use crate::apgcode::ApgcodeError;
+
+#[derive(Default)]
+pub struct IdentifyWindow {
+    open: bool,
+    apgcode: String,
+    error: Option<String>,
+}
+
+pub enum IdentifyAction {
+    Load(String),
+}
+
+impl IdentifyWindow {
+    pub fn show_result(&mut self, result: Result<String, ApgcodeError>) {
+        self.open = true;
+        match result {
+            Ok(code) => {
+                self.apgcode = code;
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e.to_string()),
+        }
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) -> Option<IdentifyAction> {
+        let mut action = None;
+
+        egui::Window::new("Identify")
+            .open(&mut self.open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                if let Some(error) = &self.error {
+                    ui.colored_label(ui.visuals().warn_fg_color, error);
+                }
+
+                ui.horizontal(|ui| {
+                    ui.label("apgcode:");
+                    ui.text_edit_singleline(&mut self.apgcode);
+                });
+
+                ui.horizontal(|ui| {
+                    if ui.button("Copy").clicked() {
+                        ui.ctx().copy_text(self.apgcode.clone());
+                    }
+                    if ui.button("Load").clicked() {
+                        action = Some(IdentifyAction::Load(self.apgcode.trim().to_string()));
+                    }
+                });
+            });
+
+        action
+    }
+}
@@ -0,0 +1,127 @@
+use std::path::PathBuf;
+
+use crate::{
+    bitmap::{ImageStyle, ImportOptions},
+    svg::{CellShape, SvgOptions},
+};
+
+pub struct ImageFileWindow {
+    open: bool,
+    path: String,
+    style: ImageStyle,
+    grid_lines: bool,
+    grid_line_color: [u8; 3],
+    shape: CellShape,
+    caption: bool,
+    import: ImportOptions,
+}
+
+pub enum ImageFileAction {
+    ExportPng(PathBuf, ImageStyle),
+    ExportSvg(PathBuf, SvgOptions),
+    Import(PathBuf, ImportOptions),
+}
+
+impl Default for ImageFileWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            path: String::new(),
+            style: ImageStyle::default(),
+            grid_lines: false,
+            grid_line_color: [200, 200, 200],
+            shape: CellShape::default(),
+            caption: false,
+            import: ImportOptions::default(),
+        }
+    }
+}
+
+impl ImageFileWindow {
+    pub fn open(&mut self) {
+        self.open = true;
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) -> Option<ImageFileAction> {
+        let mut action = None;
+
+        egui::Window::new("Image")
+            .open(&mut self.open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Path:");
+                    ui.text_edit_singleline(&mut self.path);
+                });
+                let path = PathBuf::from(self.path.trim());
+
+                ui.separator();
+                ui.label(egui::RichText::new("Export").strong());
+
+                ui.horizontal(|ui| {
+                    ui.label("Cell size:");
+                    ui.add(egui::DragValue::new(&mut self.style.cell_size).range(1..=64));
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Alive:");
+                    ui.color_edit_button_srgb(&mut self.style.alive);
+                    ui.label("Dead:");
+                    ui.color_edit_button_srgb(&mut self.style.dead);
+                });
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut self.grid_lines, "Grid lines");
+                    ui.add_enabled_ui(self.grid_lines, |ui| {
+                        ui.color_edit_button_srgb(&mut self.grid_line_color);
+                    });
+                });
+
+                let style = ImageStyle {
+                    grid_lines: self.grid_lines.then_some(self.grid_line_color),
+                    ..self.style.clone()
+                };
+
+                if ui.button("Export PNG").clicked() {
+                    action = Some(ImageFileAction::ExportPng(path.clone(), style.clone()));
+                }
+
+                ui.horizontal(|ui| {
+                    egui::ComboBox::from_label("Cell shape")
+                        .selected_text(self.shape.name())
+                        .show_ui(ui, |ui| {
+                            for shape in CellShape::ALL {
+                                ui.selectable_value(&mut self.shape, shape, shape.name());
+                            }
+                        });
+                    ui.checkbox(&mut self.caption, "Generation caption");
+                });
+
+                if ui.button("Export SVG").clicked() {
+                    let options = SvgOptions {
+                        style,
+                        shape: self.shape,
+                        caption: self.caption,
+                    };
+                    action = Some(ImageFileAction::ExportSvg(path.clone(), options));
+                }
+
+                ui.separator();
+                ui.label(egui::RichText::new("Import PNG/BMP").strong());
+
+                ui.add(egui::Slider::new(&mut self.import.threshold, 0..=255).text("Threshold"));
+                ui.horizontal(|ui| {
+                    ui.label("Pixels per cell:");
+                    ui.add(egui::DragValue::new(&mut self.import.pixels_per_cell).range(1..=32));
+                });
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut self.import.dither, "Dither");
+                    ui.checkbox(&mut self.import.invert, "Invert");
+                });
+
+                if ui.button("Import image").clicked() {
+                    action = Some(ImageFileAction::Import(path, self.import.clone()));
+                }
+            });
+
+        action
+    }
+}
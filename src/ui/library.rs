@@ -0,0 +1,115 @@
+use std::path::{Path, PathBuf};
+
+use crate::{
+    library::{Library, LibraryEntry, LibraryError, LoadedDir},
+    pattern::Pattern,
+};
+
+pub struct LibraryWindow {
+    open: bool,
+    library: Library,
+    query: String,
+    folder: String,
+}
+
+impl Default for LibraryWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            library: Library::default(),
+            query: String::new(),
+            folder: "patterns".into(),
+        }
+    }
+}
+
+pub enum LibraryAction {
+    /// Start stamping the named pattern.
+    Stamp(String, Pattern),
+    LoadFolder(PathBuf),
+}
+
+impl LibraryWindow {
+    pub fn open(&mut self) {
+        self.open = true;
+    }
+
+    pub fn load_folder(
+        &mut self,
+        dir: &Path,
+        max_size: (usize, usize),
+    ) -> Result<LoadedDir, LibraryError> {
+        self.library.load_user_dir(dir, max_size)
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) -> Option<LibraryAction> {
+        let mut action = None;
+
+        egui::Window::new("Pattern library")
+            .open(&mut self.open)
+            .default_height(400.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("🔍");
+                    ui.text_edit_singleline(&mut self.query);
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label("Folder:");
+                    ui.text_edit_singleline(&mut self.folder);
+                    if ui.button("Load").clicked() {
+                        action = Some(LibraryAction::LoadFolder(PathBuf::from(self.folder.trim())));
+                    }
+                });
+                ui.separator();
+
+                let results = self.library.search(&self.query).collect::<Vec<_>>();
+                if results.is_empty() {
+                    ui.weak("No matching patterns");
+                }
+
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    for category in categories(&results) {
+                        egui::CollapsingHeader::new(category)
+                            .default_open(true)
+                            .show(ui, |ui| {
+                                for entry in results.iter().filter(|e| e.category == category) {
+                                    if entry_button(ui, entry).clicked() {
+                                        action = Some(LibraryAction::Stamp(
+                                            entry.name.clone(),
+                                            entry.pattern.clone(),
+                                        ));
+                                    }
+                                }
+                            });
+                    }
+                });
+            });
+
+        action
+    }
+}
+
+/// Categories in the order they first appear.
+fn categories<'a>(entries: &[&'a LibraryEntry]) -> Vec<&'a str> {
+    let mut categories = Vec::new();
+    for entry in entries {
+        if !categories.contains(&entry.category.as_str()) {
+            categories.push(entry.category.as_str());
+        }
+    }
+    categories
+}
+
+fn entry_button(ui: &mut egui::Ui, entry: &LibraryEntry) -> egui::Response {
+    let size = entry
+        .pattern
+        .bounds()
+        .map_or((0, 0), |b| (b.width(), b.height()));
+    ui.button(&entry.name).on_hover_text(format!(
+        "{}×{}, {} cells",
+        size.0,
+        size.1,
+        entry.pattern.population()
+    ))
+}
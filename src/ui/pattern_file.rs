@@ -0,0 +1,55 @@
+use std::path::PathBuf;
+
+use crate::formats::PatternFormat;
+
+#[derive(Default)]
+pub struct PatternFileWindow {
+    open: bool,
+    path: String,
+    format: PatternFormat,
+}
+
+pub enum PatternFileAction {
+    Open(PathBuf),
+    Save(PathBuf, PatternFormat),
+}
+
+impl PatternFileWindow {
+    pub fn open(&mut self) {
+        self.open = true;
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) -> Option<PatternFileAction> {
+        let mut action = None;
+
+        egui::Window::new("Pattern file")
+            .open(&mut self.open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Path:");
+                    ui.text_edit_singleline(&mut self.path);
+                });
+
+                egui::ComboBox::from_label("Save as")
+                    .selected_text(self.format.name())
+                    .show_ui(ui, |ui| {
+                        for format in PatternFormat::ALL {
+                            ui.selectable_value(&mut self.format, format, format.name());
+                        }
+                    });
+
+                ui.horizontal(|ui| {
+                    let path = PathBuf::from(self.path.trim());
+                    if ui.button("Open").clicked() {
+                        action = Some(PatternFileAction::Open(path.clone()));
+                    }
+                    if ui.button("Save").clicked() {
+                        action = Some(PatternFileAction::Save(path, self.format));
+                    }
+                });
+            });
+
+        action
+    }
+}
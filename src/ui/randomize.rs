@@ -0,0 +1,76 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::soup::{SoupOptions, SplitMix64, Symmetry};
+
+#[derive(Default)]
+pub struct RandomizeWindow {
+    open: bool,
+    options: SoupOptions,
+}
+
+pub enum RandomizeAction {
+    Apply(SoupOptions),
+}
+
+impl RandomizeWindow {
+    pub fn open(&mut self) {
+        self.open = true;
+    }
+
+    /// `target` names what will be filled, e.g. "selection".
+    pub fn show(&mut self, ctx: &egui::Context, target: &str) -> Option<RandomizeAction> {
+        let mut action = None;
+
+        egui::Window::new("Randomize")
+            .open(&mut self.open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                egui::Grid::new("randomize_options").show(ui, |ui| {
+                    ui.label("Density");
+                    ui.add(
+                        egui::Slider::new(&mut self.options.density, 0.0..=1.0)
+                            .custom_formatter(|v, _| format!("{:.0}%", v * 100.0)),
+                    );
+                    ui.end_row();
+
+                    ui.label("Seed");
+                    ui.horizontal(|ui| {
+                        ui.add(egui::DragValue::new(&mut self.options.seed));
+                        if ui.button("🎲").on_hover_text("New seed").clicked() {
+                            self.options.seed = fresh_seed();
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label("Symmetry");
+                    egui::ComboBox::from_id_salt("soup_symmetry")
+                        .selected_text(self.options.symmetry.name())
+                        .show_ui(ui, |ui| {
+                            for symmetry in Symmetry::ALL {
+                                ui.selectable_value(
+                                    &mut self.options.symmetry,
+                                    symmetry,
+                                    symmetry.name(),
+                                );
+                            }
+                        });
+                    ui.end_row();
+                });
+
+                if ui.button(format!("Fill {target}")).clicked() {
+                    action = Some(RandomizeAction::Apply(self.options.clone()));
+                }
+            });
+
+        action
+    }
+}
+
+fn fresh_seed() -> u64 {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos() as u64);
+    // Scramble so seeds picked moments apart look unrelated; keep them small
+    // enough to note down.
+    SplitMix64::new(nanos).next_u64() % 1_000_000_000
+}
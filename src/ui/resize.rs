@@ -0,0 +1,112 @@
+use crate::grid::Anchor;
+
+pub struct ResizeWindow {
+    open: bool,
+    width: usize,
+    height: usize,
+    anchor: Anchor,
+    shift: (i64, i64),
+    margin: usize,
+}
+
+impl Default for ResizeWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            width: 1,
+            height: 1,
+            anchor: Anchor::default(),
+            shift: (0, 0),
+            margin: 4,
+        }
+    }
+}
+
+pub enum ResizeAction {
+    Resize {
+        width: usize,
+        height: usize,
+        anchor: Anchor,
+    },
+    Translate(i64, i64),
+    CropToLive {
+        margin: usize,
+    },
+}
+
+impl ResizeWindow {
+    /// Opens the window with the size fields set to the current grid's.
+    pub fn open(&mut self, width: usize, height: usize) {
+        self.open = true;
+        self.width = width;
+        self.height = height;
+    }
+
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        max_width: usize,
+        max_height: usize,
+    ) -> Option<ResizeAction> {
+        let mut action = None;
+
+        egui::Window::new("Resize")
+            .open(&mut self.open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(egui::RichText::new("Size").strong());
+                ui.horizontal(|ui| {
+                    ui.add(egui::DragValue::new(&mut self.width).range(1..=max_width));
+                    ui.label("×");
+                    ui.add(egui::DragValue::new(&mut self.height).range(1..=max_height));
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Anchor");
+                    egui::Grid::new("resize_anchor").show(ui, |ui| {
+                        for (i, anchor) in Anchor::ALL.into_iter().enumerate() {
+                            ui.radio_value(&mut self.anchor, anchor, "")
+                                .on_hover_text(anchor.name());
+                            if i % 3 == 2 {
+                                ui.end_row();
+                            }
+                        }
+                    });
+                });
+                if ui.button("Resize").clicked() {
+                    action = Some(ResizeAction::Resize {
+                        width: self.width,
+                        height: self.height,
+                        anchor: self.anchor,
+                    });
+                }
+
+                ui.separator();
+                ui.label(egui::RichText::new("Shift cells").strong());
+                // Shifting further than the grid is wide empties it anyway.
+                let (reach_x, reach_y) = (max_width as i64, max_height as i64);
+                ui.horizontal(|ui| {
+                    ui.label("dx");
+                    ui.add(egui::DragValue::new(&mut self.shift.0).range(-reach_x..=reach_x));
+                    ui.label("dy");
+                    ui.add(egui::DragValue::new(&mut self.shift.1).range(-reach_y..=reach_y));
+                    if ui.button("Shift").clicked() {
+                        action = Some(ResizeAction::Translate(self.shift.0, self.shift.1));
+                    }
+                });
+
+                ui.separator();
+                ui.label(egui::RichText::new("Crop to live cells").strong());
+                ui.horizontal(|ui| {
+                    ui.label("Margin");
+                    ui.add(egui::DragValue::new(&mut self.margin).range(0..=100));
+                    if ui.button("Crop").clicked() {
+                        action = Some(ResizeAction::CropToLive {
+                            margin: self.margin,
+                        });
+                    }
+                });
+            });
+
+        action
+    }
+}
@@ -0,0 +1,65 @@
+use crate::game_loop::RunGoal;
+
+pub struct RunWindow {
+    open: bool,
+    steps: u32,
+    target: u32,
+}
+
+impl Default for RunWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            steps: 100,
+            target: 1000,
+        }
+    }
+}
+
+pub enum RunAction {
+    Advance(u32),
+    GoTo(u32),
+    Until(RunGoal),
+}
+
+impl RunWindow {
+    pub fn open(&mut self) {
+        self.open = true;
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) -> Option<RunAction> {
+        let mut action = None;
+
+        egui::Window::new("Run")
+            .open(&mut self.open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                egui::Grid::new("run_commands").show(ui, |ui| {
+                    ui.label("Advance by");
+                    ui.add(egui::DragValue::new(&mut self.steps).range(1..=u32::MAX));
+                    if ui.button("Run").clicked() {
+                        action = Some(RunAction::Advance(self.steps));
+                    }
+                    ui.end_row();
+
+                    ui.label("Go to generation");
+                    ui.add(egui::DragValue::new(&mut self.target));
+                    if ui.button("Go").clicked() {
+                        action = Some(RunAction::GoTo(self.target));
+                    }
+                    ui.end_row();
+                });
+
+                ui.horizontal(|ui| {
+                    if ui.button("Until something changes").clicked() {
+                        action = Some(RunAction::Until(RunGoal::Change));
+                    }
+                    if ui.button("Until extinct or stable").clicked() {
+                        action = Some(RunAction::Until(RunGoal::Settled));
+                    }
+                });
+            });
+
+        action
+    }
+}
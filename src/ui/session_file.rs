@@ -0,0 +1,44 @@
+use std::path::PathBuf;
+
+#[derive(Default)]
+pub struct SessionFileWindow {
+    open: bool,
+    path: String,
+}
+
+pub enum SessionFileAction {
+    Open(PathBuf),
+    Save(PathBuf),
+}
+
+impl SessionFileWindow {
+    pub fn open(&mut self) {
+        self.open = true;
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) -> Option<SessionFileAction> {
+        let mut action = None;
+
+        egui::Window::new("Session")
+            .open(&mut self.open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Path:");
+                    ui.text_edit_singleline(&mut self.path);
+                });
+
+                ui.horizontal(|ui| {
+                    let path = PathBuf::from(self.path.trim());
+                    if ui.button("Open").clicked() {
+                        action = Some(SessionFileAction::Open(path.clone()));
+                    }
+                    if ui.button("Save").clicked() {
+                        action = Some(SessionFileAction::Save(path));
+                    }
+                });
+            });
+
+        action
+    }
+}
@@ -0,0 +1,64 @@
+use crate::{
+    game_loop::{PointerAction, PointerBindings},
+    stability::AutoPause,
+};
+
+#[derive(Default)]
+pub struct SettingsWindow {
+    open: bool,
+}
+
+impl SettingsWindow {
+    pub fn open(&mut self) {
+        self.open = true;
+    }
+
+    /// Edits the bindings and auto-pause options in place.
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        bindings: &mut PointerBindings,
+        auto_pause: &mut AutoPause,
+    ) {
+        egui::Window::new("Settings")
+            .open(&mut self.open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(egui::RichText::new("Mouse buttons").strong());
+
+                egui::Grid::new("pointer_bindings").show(ui, |ui| {
+                    action_combo(ui, "Right click", &mut bindings.right);
+                    ui.end_row();
+                    action_combo(ui, "Both buttons", &mut bindings.both);
+                    ui.end_row();
+                });
+
+                if ui.button("Reset to defaults").clicked() {
+                    *bindings = PointerBindings::default();
+                }
+
+                ui.separator();
+                ui.label(egui::RichText::new("Pause playback when").strong());
+                ui.checkbox(&mut auto_pause.extinct, "Everything has died");
+                ui.checkbox(&mut auto_pause.stable, "Nothing changes any more");
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut auto_pause.periodic, "It repeats with period up to");
+                    ui.add_enabled(
+                        auto_pause.periodic,
+                        egui::DragValue::new(&mut auto_pause.max_period).range(2..=1000),
+                    );
+                });
+            });
+    }
+}
+
+fn action_combo(ui: &mut egui::Ui, label: &str, action: &mut PointerAction) {
+    ui.label(label);
+    egui::ComboBox::from_id_salt(label)
+        .selected_text(action.name())
+        .show_ui(ui, |ui| {
+            for option in PointerAction::ALL {
+                ui.selectable_value(action, option, option.name());
+            }
+        });
+}
@@ -0,0 +1,79 @@
+use crate::history::GenerationHistory;
+
+const HEIGHT: f32 = 32.0;
+
+/// Strip showing the recorded generations with a population sparkline and
+/// keyframe ticks; click or drag to pick a generation.
+pub struct TimelineView<'a> {
+    history: &'a GenerationHistory,
+    current: u32,
+}
+
+impl<'a> TimelineView<'a> {
+    pub fn new(history: &'a GenerationHistory, current: u32) -> Self {
+        Self { history, current }
+    }
+
+    /// Returns the generation picked this frame, if it differs from the
+    /// current one.
+    pub fn show(self, ui: &mut egui::Ui) -> Option<u32> {
+        let size = egui::vec2(ui.available_width(), HEIGHT);
+        let (rect, response) = ui.allocate_exact_size(size, egui::Sense::click_and_drag());
+        let painter = ui.painter_at(rect);
+        let visuals = ui.visuals();
+        painter.rect_filled(rect, 2.0, visuals.extreme_bg_color);
+
+        let range = self.history.range()?;
+        let (first, last) = (*range.start(), *range.end());
+        let span = (last - first).max(1) as f32;
+        let x_of =
+            |generation: u32| rect.left() + (generation - first) as f32 / span * rect.width();
+
+        // Population sparkline, scaled to the busiest generation and keeping
+        // the peak of each pixel column so long runs stay cheap to draw.
+        let populations = self.history.populations();
+        let peak = populations.iter().copied().max().unwrap_or(0).max(1) as f32;
+        let columns = (rect.width() as usize).clamp(1, populations.len());
+        let chunk_size = populations.len().div_ceil(columns);
+        let points = populations
+            .chunks(chunk_size)
+            .enumerate()
+            .map(|(i, chunk)| {
+                let population = chunk.iter().copied().max().unwrap_or(0);
+                let generation = first + (i * chunk_size) as u32;
+                let y = rect.bottom() - 2.0 - population as f32 / peak * (rect.height() - 8.0);
+                egui::pos2(x_of(generation), y)
+            })
+            .collect::<Vec<_>>();
+        painter.add(egui::Shape::line(
+            points,
+            egui::Stroke::new(1.0, visuals.text_color()),
+        ));
+
+        let tick = egui::Stroke::new(1.0, visuals.weak_text_color());
+        for generation in self.history.keyframe_generations() {
+            let x = x_of(generation);
+            painter.line_segment(
+                [egui::pos2(x, rect.top()), egui::pos2(x, rect.top() + 4.0)],
+                tick,
+            );
+        }
+
+        if range.contains(&self.current) {
+            let x = x_of(self.current);
+            painter.line_segment(
+                [egui::pos2(x, rect.top()), egui::pos2(x, rect.bottom())],
+                egui::Stroke::new(2.0, visuals.selection.stroke.color),
+            );
+        }
+
+        let response = response.on_hover_text(format!("gen {first} – {last}"));
+        let pointer = response.interact_pointer_pos()?;
+        if !(response.clicked() || response.dragged()) {
+            return None;
+        }
+        let fraction = ((pointer.x - rect.left()) / rect.width()).clamp(0.0, 1.0);
+        let picked = first + (fraction * span).round() as u32;
+        (picked.min(last) != self.current).then_some(picked.min(last))
+    }
+}
@@ -0,0 +1,155 @@
+use std::collections::VecDeque;
+
+use crate::{game::Game, snapshot::Snapshot};
+
+/// Default memory budget for undo snapshots.
+pub const DEFAULT_MAX_BYTES: usize = 16 * 1024 * 1024;
+
+/// Snapshots taken before each edit, dropping the oldest once they exceed
+/// the memory budget.
+#[derive(Debug)]
+pub struct UndoStack {
+    undo: VecDeque<Snapshot>,
+    redo: Vec<Snapshot>,
+    undo_bytes: usize,
+    max_bytes: usize,
+}
+
+impl Default for UndoStack {
+    fn default() -> Self {
+        UndoStack::new(DEFAULT_MAX_BYTES)
+    }
+}
+
+impl UndoStack {
+    pub fn new(max_bytes: usize) -> Self {
+        UndoStack {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            undo_bytes: 0,
+            max_bytes,
+        }
+    }
+
+    /// Remembers the game as it was before an edit and forgets anything that
+    /// could have been redone.
+    pub fn record(&mut self, game: &Game) {
+        self.redo.clear();
+        self.push_undo(Snapshot::capture(game));
+    }
+
+    /// Returns the game before the last recorded edit, keeping `current` for redo.
+    pub fn undo(&mut self, current: &Game) -> Option<Game> {
+        let snapshot = self.undo.pop_back()?;
+        self.undo_bytes -= snapshot.size_bytes();
+        self.redo.push(Snapshot::capture(current));
+        Some(snapshot.restore())
+    }
+
+    pub fn redo(&mut self, current: &Game) -> Option<Game> {
+        let snapshot = self.redo.pop()?;
+        self.push_undo(Snapshot::capture(current));
+        Some(snapshot.restore())
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    fn push_undo(&mut self, snapshot: Snapshot) {
+        self.undo_bytes += snapshot.size_bytes();
+        self.undo.push_back(snapshot);
+
+        while self.undo_bytes > self.max_bytes && self.undo.len() > 1 {
+            let oldest = self
+                .undo
+                .pop_front()
+                .expect("push_undo: stack is not empty");
+            self.undo_bytes -= oldest.size_bytes();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+
+    fn game_with_cell(cell: (usize, usize)) -> Game {
+        let mut grid = Grid::new(10, 10);
+        let _ = grid.set_cell_at_coord(cell, true);
+        Game::new(grid)
+    }
+
+    fn live_cells(game: &Game) -> Vec<(usize, usize)> {
+        game.grid.live_cells().collect()
+    }
+
+    #[test]
+    fn test_undo_then_redo() {
+        let mut stack = UndoStack::default();
+        let before = game_with_cell((1, 1));
+        let after = game_with_cell((2, 2));
+        stack.record(&before);
+
+        let undone = stack.undo(&after).unwrap();
+        let redone = stack.redo(&undone).unwrap();
+
+        assert_eq!(live_cells(&undone), vec![(1, 1)]);
+        assert_eq!(live_cells(&redone), vec![(2, 2)]);
+        assert!(stack.can_undo());
+        assert!(!stack.can_redo());
+    }
+
+    #[test]
+    fn test_recording_clears_redo() {
+        let mut stack = UndoStack::default();
+        stack.record(&game_with_cell((1, 1)));
+        let _ = stack.undo(&game_with_cell((2, 2)));
+
+        stack.record(&game_with_cell((1, 1)));
+
+        assert!(!stack.can_redo());
+    }
+
+    #[test]
+    fn test_oldest_snapshots_dropped_over_budget() {
+        // Each snapshot keeps one word behind its run header.
+        let mut stack = UndoStack::new(3 * (size_of::<Snapshot>() + 16));
+        for x in 0..5 {
+            stack.record(&game_with_cell((x, 0)));
+        }
+
+        let mut undone = Vec::new();
+        let mut current = game_with_cell((9, 9));
+        while let Some(game) = stack.undo(&current) {
+            undone.push(live_cells(&game)[0]);
+            current = game;
+        }
+
+        assert_eq!(undone, vec![(4, 0), (3, 0), (2, 0)]);
+    }
+
+    #[test]
+    fn test_playing_empty_grid_stays_within_budget() {
+        let mut stack = UndoStack::new(3 * size_of::<Snapshot>());
+        let mut game = Game::new(Grid::new(10, 10));
+        for _ in 0..1000 {
+            stack.record(&game);
+            game.tick();
+        }
+
+        let mut kept = 0;
+        while let Some(undone) = stack.undo(&game) {
+            game = undone;
+            kept += 1;
+        }
+
+        assert_eq!(kept, 3);
+        assert_eq!(game.generation(), 997);
+    }
+}